@@ -0,0 +1,2 @@
+pub mod selfplay;
+pub mod packed_board;
\ No newline at end of file
@@ -0,0 +1,81 @@
+//the Pod derive generates a padding check that is never called
+#![allow(dead_code)]
+
+use cozy_chess::*;
+
+use bytemuck::{Pod, Zeroable};
+
+/*
+Marlinformat packed board, 32 bytes per position
+https://github.com/jnlt3/marlinflow
+*/
+
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+#[repr(C)]
+pub struct PackedBoard {
+	occupancy: u64,
+	pieces: [u8; 16],
+	stm_ep_square: u8,
+	halfmove_clock: u8,
+	fullmove_number: u16,
+	eval: i16,
+	wdl: u8,
+	extra: u8
+}
+
+impl PackedBoard {
+	//score is relative to white, result is 0.0, 0.5 or 1.0 from white's point of view
+	pub fn pack(board: &Board, score: i32, result: f32) -> PackedBoard {
+		let occupied = board.occupied();
+		let mut pieces = [0u8; 16];
+
+		for (i, square) in occupied.into_iter().enumerate() {
+			let piece = board.piece_on(square).unwrap();
+			let color = board.color_on(square).unwrap();
+
+			let mut piece_bits = piece as u8;
+
+			//rooks that can still castle are marked as unmoved rooks
+			if piece == Piece::Rook && square.rank() == Rank::First.relative_to(color) {
+				let rights = board.castle_rights(color);
+				if rights.short == Some(square.file()) || rights.long == Some(square.file()) {
+					piece_bits = Self::UNMOVED_ROOK;
+				}
+			}
+
+			if color == Color::Black {
+				piece_bits |= Self::BLACK;
+			}
+
+			pieces[i / 2] |= piece_bits << ((i % 2) * 4);
+		}
+
+		let ep_square = match board.en_passant() {
+			Some(file) => Square::new(file, Rank::Sixth.relative_to(board.side_to_move())) as u8,
+			None => Self::NO_SQUARE
+		};
+
+		let stm = if board.side_to_move() == Color::Black { 0x80 } else { 0 };
+
+		PackedBoard {
+			occupancy: occupied.0.to_le(),
+			pieces,
+			stm_ep_square: stm | ep_square,
+			halfmove_clock: board.halfmove_clock(),
+			fullmove_number: board.fullmove_number().to_le(),
+			eval: (score.clamp(i16::MIN as i32, i16::MAX as i32) as i16).to_le(),
+			wdl: (result * 2.0) as u8,
+			extra: 0
+		}
+	}
+
+	pub fn as_bytes(&self) -> &[u8] {
+		bytemuck::bytes_of(self)
+	}
+}
+
+impl PackedBoard {
+	const UNMOVED_ROOK: u8 = 6;
+	const BLACK: u8 = 8;
+	const NO_SQUARE: u8 = 64;
+}
@@ -0,0 +1,309 @@
+use cozy_chess::*;
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::search::search_master::*;
+use crate::eval::score::*;
+use crate::movegen::movegen::*;
+use crate::movegen::movesorter::*;
+use crate::datagen::packed_board::*;
+use crate::eval::terms::*;
+
+/*
+Self-play data generation
+Usage: datagen [threads N] [games N] [nodes N] [plies N] [out PATH]
+Writes PATH.txt (FEN | score | result) and PATH.bin (marlinformat)
+*/
+
+const HASH: u32 = 16;
+const THREADS: usize = 1;
+const GAMES: u64 = 1000;
+const NODES: u64 = 5000;
+const RANDOM_PLIES: usize = 8;
+const OUT: &str = "trinket_data";
+const STACK_SIZE: usize = 16 * 1024 * 1024;
+
+//scores are in centipawns from white's point of view
+const OPENING_SCORE_LIMIT: i32 = 1000;
+const WIN_ADJ_SCORE: i32 = 2500;
+const WIN_ADJ_PLIES: u32 = 4;
+const DRAW_ADJ_SCORE: i32 = 10;
+const DRAW_ADJ_PLIES: u32 = 12;
+const DRAW_ADJ_MIN_PLY: u32 = 160;
+const MAX_PLIES: u32 = 400;
+
+pub struct Rng(u64);
+
+impl Rng {
+	pub fn new(seed: u64) -> Rng {
+		Rng(seed | 1)
+	}
+
+	//xorshift64*
+	pub fn next(&mut self) -> u64 {
+		self.0 ^= self.0 >> 12;
+		self.0 ^= self.0 << 25;
+		self.0 ^= self.0 >> 27;
+		self.0.wrapping_mul(0x2545F4914F6CDD1D)
+	}
+
+	pub fn below(&mut self, n: usize) -> usize {
+		(self.next() % n as u64) as usize
+	}
+}
+
+struct Output {
+	text: BufWriter<File>,
+	binary: BufWriter<File>
+}
+
+struct Progress {
+	games: AtomicU64,
+	positions: AtomicU64,
+	start: Instant
+}
+
+pub fn datagen(args: Vec<String>, eval_params: &Arc<EvalParams>) {
+	let mut threads = THREADS;
+	let mut games = GAMES;
+	let mut nodes = NODES;
+	let mut random_plies = RANDOM_PLIES;
+	let mut out = String::from(OUT);
+
+	for i in (0..args.len()).step_by(2) {
+		let value = match args.get(i + 1) {
+			Some(value) => value,
+			None => break
+		};
+
+		match args[i].as_str() {
+			"threads" => threads = value.parse().unwrap(),
+			"games" => games = value.parse().unwrap(),
+			"nodes" => nodes = value.parse().unwrap(),
+			"plies" => random_plies = value.parse().unwrap(),
+			"out" => out = value.clone(),
+			_ => println!("Unknown datagen option: {}", args[i])
+		}
+	}
+
+	println!("Generating {} games on {} threads at {} nodes per move", games, threads, nodes);
+
+	let output = match (File::create(format!("{}.txt", out)), File::create(format!("{}.bin", out))) {
+		(Ok(text), Ok(binary)) => Mutex::new(Output {
+			text: BufWriter::new(text),
+			binary: BufWriter::new(binary)
+		}),
+		(Err(e), _) | (_, Err(e)) => {
+			println!("Could not create the output files {}: {}", out, e);
+			return;
+		}
+	};
+
+	//set by the first thread that fails to write, so the others stop instead of producing a truncated set
+	let failed = AtomicBool::new(false);
+
+	let progress = Progress {
+		games: AtomicU64::new(0),
+		positions: AtomicU64::new(0),
+		start: Instant::now()
+	};
+
+	let seed = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos() as u64;
+
+	thread::scope(|scope| {
+		for i in 0..threads {
+			let thread_games = games / threads as u64 + ((i as u64) < games % threads as u64) as u64;
+			let output = &output;
+			let progress = &progress;
+			let failed = &failed;
+
+			thread::Builder::new().stack_size(STACK_SIZE).spawn_scoped(scope, move || {
+				let mut rng = Rng::new(seed ^ (i as u64 + 1).wrapping_mul(0x9E3779B97F4A7C15));
+
+				for _ in 0..thread_games {
+					if failed.load(Ordering::Relaxed) {
+						break;
+					}
+
+					let (positions, result) = play_game(&mut rng, nodes, random_plies, eval_params);
+					if let Err(e) = write_game(output, &positions, result) {
+						println!("Failed to write data: {}", e);
+						failed.store(true, Ordering::Relaxed);
+						break;
+					}
+
+					let finished = progress.games.fetch_add(1, Ordering::Relaxed) + 1;
+					let total_positions = progress.positions.fetch_add(positions.len() as u64, Ordering::Relaxed) + positions.len() as u64;
+
+					if finished.is_multiple_of(100) || finished == games {
+						let elapsed = progress.start.elapsed().as_secs_f32();
+						println!("games {} positions {} pos/s {}", finished, total_positions, (total_positions as f32 / elapsed) as u64);
+					}
+				}
+			}).unwrap();
+		}
+	});
+
+	if failed.load(Ordering::Relaxed) {
+		println!("Stopped early after a write error, {} is incomplete", out);
+		return;
+	}
+
+	let mut output = output.lock().unwrap();
+	if let Err(e) = output.text.flush().and_then(|_| output.binary.flush()) {
+		println!("Failed to write data: {}", e);
+	}
+}
+
+fn write_game(output: &Mutex<Output>, positions: &[(Board, i32)], result: f32) -> io::Result<()> {
+	let mut output = output.lock().unwrap();
+
+	for (board, score) in positions {
+		writeln!(output.text, "{} | {} | {:.1}", board, score, result)?;
+		output.binary.write_all(PackedBoard::pack(board, *score, result).as_bytes())?;
+	}
+
+	Ok(())
+}
+
+//plays one self-play game, returning the recorded quiet positions with white-relative scores and the white-relative result
+fn play_game(rng: &mut Rng, nodes: u64, random_plies: usize, eval_params: &Arc<EvalParams>) -> (Vec<(Board, i32)>, f32) {
+	let mut engine = new_engine(eval_params);
+
+	//pick a random opening that is neither over nor too unbalanced
+	loop {
+		engine.boardwrapper.set_board(Board::default());
+		engine.my_past_positions = vec![engine.boardwrapper.board.hash()];
+
+		let mut ok = true;
+		for _ in 0..random_plies + rng.below(2) {
+			let moves = legal_moves(&engine.boardwrapper.board);
+			if moves.is_empty() {
+				ok = false;
+				break;
+			}
+
+			play(&mut engine, moves[rng.below(moves.len())]);
+		}
+
+		if ok && engine.boardwrapper.board.status() == GameStatus::Ongoing {
+			search(&mut engine, nodes);
+			if engine.score.abs() <= OPENING_SCORE_LIMIT {
+				break;
+			}
+		}
+
+		engine = new_engine(eval_params);
+	}
+
+	let mut positions = Vec::new();
+	let mut win_plies = 0;
+	let mut draw_plies = 0;
+	let mut ply = 0;
+
+	let result = loop {
+		let board = engine.boardwrapper.board.clone();
+
+		match board.status() {
+			GameStatus::Won => break if board.side_to_move() == Color::White { 0.0 } else { 1.0 },
+			GameStatus::Drawn => break 0.5,
+			GameStatus::Ongoing => {}
+		}
+
+		if is_repetition(&engine.my_past_positions) || is_insufficient_material(&board) || ply >= MAX_PLIES {
+			break 0.5;
+		}
+
+		search(&mut engine, nodes);
+
+		let mv = engine.best_move.unwrap();
+		let score = if board.side_to_move() == Color::White { engine.score } else { -engine.score };
+
+		//adjudicate decided games
+		if score.abs() >= WIN_ADJ_SCORE {
+			win_plies += 1;
+			if win_plies >= WIN_ADJ_PLIES {
+				break if score > 0 { 1.0 } else { 0.0 };
+			}
+		} else {
+			win_plies = 0;
+		}
+
+		if ply >= DRAW_ADJ_MIN_PLY && score.abs() <= DRAW_ADJ_SCORE {
+			draw_plies += 1;
+			if draw_plies >= DRAW_ADJ_PLIES {
+				break 0.5;
+			}
+		} else {
+			draw_plies = 0;
+		}
+
+		//only record quiet positions
+		let is_capture = board.color_on(mv.to) == Some(!board.side_to_move());
+		if board.checkers().is_empty() && !is_capture && mv.promotion.is_none() && score.abs() < Score::MATE_THRESHOLD {
+			let (static_eval, qsearch_eval) = engine.qsearch();
+			if static_eval == qsearch_eval {
+				positions.push((board, score));
+			}
+		}
+
+		play(&mut engine, mv);
+		ply += 1;
+	};
+
+	(positions, result)
+}
+
+fn new_engine(eval_params: &Arc<EvalParams>) -> Engine<'static> {
+	let mut engine = Engine::new(HASH, 1);
+	engine.print_info = false;
+	engine.boardwrapper.eval_params = eval_params.clone();
+
+	engine
+}
+
+fn search(engine: &mut Engine, nodes: u64) {
+	let mut time_control = TimeControl::new();
+	time_control.nodes = Some(nodes);
+
+	engine.go(time_control, Arc::new(AtomicBool::new(false)));
+}
+
+pub fn play(engine: &mut Engine, mv: Move) {
+	let board = &engine.boardwrapper.board;
+	let movetype = if board.color_on(mv.to) == Some(!board.side_to_move()) {
+		MoveType::Loud
+	} else {
+		MoveType::Quiet
+	};
+
+	engine.boardwrapper.play_unchecked(&mut SortedMove::new(mv, 0, movetype));
+	engine.my_past_positions.push(engine.boardwrapper.board.hash());
+}
+
+pub fn legal_moves(board: &Board) -> Vec<Move> {
+	let mut moves = Vec::with_capacity(64);
+	board.generate_moves(|piece_moves| {
+		moves.extend(piece_moves);
+		false
+	});
+
+	moves
+}
+
+pub fn is_repetition(past_positions: &[u64]) -> bool {
+	let current = past_positions[past_positions.len() - 1];
+	past_positions.iter().filter(|&&hash| hash == current).count() >= 3
+}
+
+pub fn is_insufficient_material(board: &Board) -> bool {
+	let heavy = board.pieces(Piece::Pawn) | board.pieces(Piece::Rook) | board.pieces(Piece::Queen);
+	let minors = board.pieces(Piece::Knight) | board.pieces(Piece::Bishop);
+
+	heavy.is_empty() && minors.len() <= 1
+}
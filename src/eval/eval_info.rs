@@ -51,6 +51,31 @@ pub const ROOK_SEMI_FILE_BONUS: Score = S!(  15,   16);
 pub const CONNECTED_PASSED_PAWN: Score = S!(  11,    5);
 pub const KING_ON_SEMI_OPEN_FILE: Score = S!(   3,    2);
 pub const KING_ON_OPEN_FILE: Score = S!( -54,   -3);
+pub const PAWN_PUSH_THREAT: Score = S!(  15,   12);
+pub const THREAT_ON_MOVE: Score = S!(  20,   15);
+
+pub const KING_DANGER: [Score; 40] = [
+    S!(   0,    0), S!(   0,    0), S!(  -1,    0), S!(  -2,    0), S!(  -4,   -1), S!(  -7,   -2), S!( -10,   -3), S!( -14,   -4), 
+    S!( -19,   -6), S!( -24,   -8), S!( -30,  -10), S!( -36,  -12), S!( -43,  -14), S!( -50,  -16), S!( -58,  -19), S!( -67,  -22), 
+    S!( -76,  -25), S!( -86,  -28), S!( -97,  -32), S!(-108,  -36), S!(-120,  -40), S!(-132,  -44), S!(-145,  -48), S!(-158,  -52), 
+    S!(-172,  -57), S!(-187,  -62), S!(-202,  -67), S!(-218,  -72), S!(-235,  -78), S!(-252,  -84), S!(-270,  -90), S!(-288,  -96), 
+    S!(-307, -102), S!(-326, -108), S!(-346, -115), S!(-367, -122), S!(-388, -129), S!(-410, -136), S!(-433, -144), S!(-456, -152), 
+];
+pub const PAWN_SHIELD: [Score; 4] = [
+    S!(  18,   -2), S!(  10,    0), S!(   3,    0), S!( -20,    2), 
+];
+pub const PAWN_STORM: [Score; 4] = [
+    S!(  -8,    0), S!( -22,   -4), S!( -12,   -2), S!(  -5,    0), 
+];
+pub const THREAT_BY_PAWN: [Score; 5] = [
+    S!(   0,    0), S!(  50,   30), S!(  50,   30), S!(  60,   40), S!(  60,   40), 
+];
+pub const THREAT_BY_LESSER: [Score; 5] = [
+    S!(   0,    0), S!(   0,    0), S!(   0,    0), S!(  40,   25), S!(  45,   30), 
+];
+pub const HANGING: [Score; 5] = [
+    S!(  10,   15), S!(  30,   20), S!(  30,   20), S!(  35,   25), S!(  40,   30), 
+];
  
 //PSTs
 pub const P: [Score; 64] = [
@@ -4,24 +4,40 @@
 
 use cozy_chess::*;
 use crate::eval::score::*;
-use crate::eval::eval_info::*;
 use crate::eval::draw_oracle::*;
+use crate::eval::terms::*;
 
 struct Evaluator<'a> {
 	board: &'a Board,
-	color: Color
+	color: Color,
+	params: &'a EvalParams,
+	//attack maps of both colors indexed by color, shared by the white and black evaluators
+	attacks: &'a [[BitBoard; 6]; 2],
+	trace: Option<&'a mut Trace>
 }
 
 impl Evaluator<'_> {
-	fn new(board: &Board, color: Color) -> Evaluator {
+	fn new<'a>(board: &'a Board, color: Color, params: &'a EvalParams, attacks: &'a [[BitBoard; 6]; 2], trace: Option<&'a mut Trace>) -> Evaluator<'a> {
 		Evaluator {
-			board: board,
-			color: color
+			board,
+			color,
+			params,
+			attacks,
+			trace
+		}
+	}
+
+	//records that a weight was applied once for our color, white counting positive and black negative
+	fn trace(&mut self, term: impl FnOnce(&mut Trace) -> &mut i32) {
+		let sign = if self.color == Color::White { 1 } else { -1 };
+
+		if let Some(trace) = self.trace.as_deref_mut() {
+			*term(trace) += sign;
 		}
 	}
 
 	//evaluates piece weights + PST with tapered eval
-	fn eval(&self) -> i32 {
+	fn eval(&mut self) -> i32 {
 		let phase = self.calculate_phase();
 		let mut sum = 0;
 
@@ -33,27 +49,38 @@ impl Evaluator<'_> {
 
 				match piece {
 					Piece::Pawn => {
-						sum += PAWN.eval(phase);
-						sum += P[square_idx].eval(phase);
+						sum += self.params.pawn.eval(phase);
+						sum += self.params.p[square_idx].eval(phase);
+						self.trace(|t| &mut t.pawn);
+						self.trace(|t| &mut t.p[square_idx]);
 					},
 					Piece::Knight => {
-						sum += KNIGHT.eval(phase);
-						sum += N[square_idx].eval(phase);
+						sum += self.params.knight.eval(phase);
+						sum += self.params.n[square_idx].eval(phase);
+						self.trace(|t| &mut t.knight);
+						self.trace(|t| &mut t.n[square_idx]);
 					},
 					Piece::Bishop => {
-						sum += BISHOP.eval(phase);
-						sum += B[square_idx].eval(phase);
+						sum += self.params.bishop.eval(phase);
+						sum += self.params.b[square_idx].eval(phase);
+						self.trace(|t| &mut t.bishop);
+						self.trace(|t| &mut t.b[square_idx]);
 					},
 					Piece::Rook => {
-						sum += ROOK.eval(phase);
-						sum += R[square_idx].eval(phase);
+						sum += self.params.rook.eval(phase);
+						sum += self.params.r[square_idx].eval(phase);
+						self.trace(|t| &mut t.rook);
+						self.trace(|t| &mut t.r[square_idx]);
 					},
 					Piece::Queen => {
-						sum += QUEEN.eval(phase);
-						sum += Q[square_idx].eval(phase);
+						sum += self.params.queen.eval(phase);
+						sum += self.params.q[square_idx].eval(phase);
+						self.trace(|t| &mut t.queen);
+						self.trace(|t| &mut t.q[square_idx]);
 					},
 					Piece::King => {
-						sum += K[square_idx].eval(phase);
+						sum += self.params.k[square_idx].eval(phase);
+						self.trace(|t| &mut t.k[square_idx]);
 					}
 				}
 			}
@@ -69,22 +96,36 @@ impl Evaluator<'_> {
 		sum += self.isolated_pawn(phase);
 		sum += self.rook_files(phase);
 		sum += self.king_on_risky_file(phase);
+		sum += self.king_safety(phase);
+		sum += self.king_shelter(phase);
+		sum += self.threats(phase);
 
 		sum
 	}
 
-	fn get_mobility_weight(&self, piece: Piece) -> &[Score] {
+	fn get_mobility_weight(params: &EvalParams, piece: Piece) -> &[Score] {
+		match piece {
+			Piece::Pawn => &params.pawn_mobility,
+			Piece::Knight => &params.knight_mobility,
+			Piece::Bishop => &params.bishop_mobility,
+			Piece::Rook => &params.rook_mobility,
+			Piece::Queen => &params.queen_mobility,
+			Piece::King => &params.king_mobility
+		}
+	}
+
+	fn get_mobility_trace(trace: &mut Trace, piece: Piece) -> &mut [i32] {
 		match piece {
-			Piece::Pawn => &PAWN_MOBILITY,
-			Piece::Knight => &KNIGHT_MOBILITY,
-			Piece::Bishop => &BISHOP_MOBILITY,
-			Piece::Rook => &ROOK_MOBILITY,
-			Piece::Queen => &QUEEN_MOBILITY,
-			Piece::King => &KING_MOBILITY
+			Piece::Pawn => &mut trace.pawn_mobility,
+			Piece::Knight => &mut trace.knight_mobility,
+			Piece::Bishop => &mut trace.bishop_mobility,
+			Piece::Rook => &mut trace.rook_mobility,
+			Piece::Queen => &mut trace.queen_mobility,
+			Piece::King => &mut trace.king_mobility
 		}
 	}
 
-	fn king_on_risky_file(&self, phase: i32) -> i32 {
+	fn king_on_risky_file(&mut self, phase: i32) -> i32 {
 		let mut penalty = 0;
 
 		let pawns = self.board.pieces(Piece::Pawn);
@@ -92,15 +133,205 @@ impl Evaluator<'_> {
 		let our_king_file = self.board.king(self.color).file();
 
 		if (pawns & our_king_file.bitboard()).is_empty() {
-			penalty += KING_ON_OPEN_FILE.eval(phase);
+			penalty += self.params.king_on_open_file.eval(phase);
+			self.trace(|t| &mut t.king_on_open_file);
 		} else if (our_pawns & our_king_file.bitboard()).is_empty() {
-			penalty += KING_ON_SEMI_OPEN_FILE.eval(phase);
+			penalty += self.params.king_on_semi_open_file.eval(phase);
+			self.trace(|t| &mut t.king_on_semi_open_file);
 		}
 
 		penalty
 	}
 
-	fn connected_pawns(&self, phase: i32) -> i32 {
+	//attack units on our king zone, looked up in a tapered danger table
+	//the units are fixed weights and the table is what the tuner fits, so the penalty can grow faster than the attack
+	fn king_safety(&mut self, phase: i32) -> i32 {
+		let occupied = self.board.occupied();
+		let our_pieces = self.board.colors(self.color);
+		let enemy_pieces = self.board.colors(!self.color);
+		let king = self.board.king(self.color);
+
+		let ring = get_king_moves(king);
+		let zone = ring | king.bitboard() | Self::forward(ring, self.color);
+
+		//squares covered by our pieces other than the king
+		let defended = self.attacks[self.color as usize][..5].iter().fold(BitBoard::EMPTY, |all, &attacks| all | attacks);
+
+		let mut attackers = 0;
+		let mut units = 0;
+		let mut enemy_attacks = BitBoard::EMPTY;
+
+		for &piece in &[Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen] {
+			//squares this kind of piece could check from without being taken by anything but the king
+			let safe_checks = Self::piece_attacks(piece, king, self.color, occupied) & !enemy_pieces & !defended;
+
+			for square in enemy_pieces & self.board.pieces(piece) {
+				let attacks = Self::piece_attacks(piece, square, !self.color, occupied);
+				let zone_attacks = (attacks & zone).len() as i32;
+				enemy_attacks |= attacks;
+
+				if zone_attacks > 0 {
+					attackers += 1;
+					units += Self::ATTACKER_UNITS[piece as usize] + zone_attacks * Self::ZONE_ATTACK_UNITS;
+				}
+
+				units += (attacks & safe_checks).len() as i32 * Self::SAFE_CHECK_UNITS[piece as usize];
+			}
+		}
+
+		//a lone attacker can not mate
+		if attackers < Self::KING_ATTACKERS_MIN {
+			return 0;
+		}
+
+		//ring squares that only the king defends
+		units += (ring & enemy_attacks & !defended).len() as i32 * Self::WEAK_RING_UNITS;
+
+		let index = (units as usize).min(self.params.king_danger.len() - 1);
+		self.trace(|t| &mut t.king_danger[index]);
+		self.params.king_danger[index].eval(phase)
+	}
+
+	//our pawns in front of the king on its file and the neighbouring ones, and enemy pawns advancing on them
+	fn king_shelter(&mut self, phase: i32) -> i32 {
+		let mut score = 0;
+		let pawns = self.board.pieces(Piece::Pawn);
+		let our_pawns = pawns & self.board.colors(self.color);
+		let enemy_pawns = pawns & self.board.colors(!self.color);
+		let king = self.board.king(self.color);
+		let king_file = king.file() as usize;
+
+		for file in king_file.saturating_sub(1)..=(king_file + 1).min(7) {
+			let file_bb = File::ALL[file].bitboard();
+
+			//a shield pawn too far up the board counts as missing
+			let shield = match self.ranks_ahead(our_pawns & file_bb, king) {
+				Some(distance) if distance <= Self::SHIELD_DISTANCE => distance - 1,
+				_ => Self::SHIELD_DISTANCE
+			};
+
+			score += self.params.pawn_shield[shield].eval(phase);
+			self.trace(|t| &mut t.pawn_shield[shield]);
+
+			if let Some(distance) = self.ranks_ahead(enemy_pawns & file_bb, king).filter(|&distance| distance <= Self::STORM_DISTANCE) {
+				score += self.params.pawn_storm[distance - 1].eval(phase);
+				self.trace(|t| &mut t.pawn_storm[distance - 1]);
+			}
+		}
+
+		score
+	}
+
+	//threats our pieces make against the enemy's, from the full attack maps of both colors
+	fn threats(&mut self, phase: i32) -> i32 {
+		let mut score = 0;
+		let mut has_threat = false;
+
+		let our_attacks = &self.attacks[self.color as usize];
+		let enemy_attacks = &self.attacks[!self.color as usize];
+		let our_attacked = our_attacks.iter().fold(BitBoard::EMPTY, |all, &attacks| all | attacks);
+		let enemy_attacked = enemy_attacks.iter().fold(BitBoard::EMPTY, |all, &attacks| all | attacks);
+
+		let enemy_pieces = self.board.colors(!self.color) & !self.board.pieces(Piece::King);
+		let enemy_non_pawns = enemy_pieces & !self.board.pieces(Piece::Pawn);
+
+		for victim in enemy_pieces {
+			let piece = self.board.piece_on(victim).unwrap();
+			let index = piece as usize;
+
+			//attacked by something worth less, which wins material even if the piece is defended
+			let lesser = Piece::ALL[1..5].iter()
+				.filter(|&&attacker| Self::THREAT_VALUES[attacker as usize] < Self::THREAT_VALUES[index])
+				.any(|&attacker| our_attacks[attacker as usize].has(victim));
+
+			if piece != Piece::Pawn && our_attacks[Piece::Pawn as usize].has(victim) {
+				score += self.params.threat_by_pawn[index].eval(phase);
+				self.trace(|t| &mut t.threat_by_pawn[index]);
+				has_threat = true;
+			} else if lesser {
+				score += self.params.threat_by_lesser[index].eval(phase);
+				self.trace(|t| &mut t.threat_by_lesser[index]);
+				has_threat = true;
+			}
+
+			if our_attacked.has(victim) && !enemy_attacked.has(victim) {
+				score += self.params.hanging[index].eval(phase);
+				self.trace(|t| &mut t.hanging[index]);
+				has_threat = true;
+			}
+		}
+
+		//pawn pushes to squares the enemy pawns do not cover that would attack a piece
+		let our_pawns = self.board.colors(self.color) & self.board.pieces(Piece::Pawn);
+		let empty = !self.board.occupied();
+		let mut pushes = Self::forward(our_pawns, self.color) & empty;
+		pushes |= Self::forward(pushes & Rank::Third.relative_to(self.color).bitboard(), self.color) & empty;
+		pushes &= !enemy_attacks[Piece::Pawn as usize] & (our_attacked | !enemy_attacked);
+
+		let mut push_threats = BitBoard::EMPTY;
+		for square in pushes {
+			push_threats |= get_pawn_attacks(square, self.color);
+		}
+
+		for _ in push_threats & enemy_non_pawns & !our_attacks[Piece::Pawn as usize] {
+			score += self.params.pawn_push_threat.eval(phase);
+			self.trace(|t| &mut t.pawn_push_threat);
+		}
+
+		//with the move we get to cash in a threat first
+		if has_threat && self.board.side_to_move() == self.color {
+			score += self.params.threat_on_move.eval(phase);
+			self.trace(|t| &mut t.threat_on_move);
+		}
+
+		score
+	}
+
+	//squares attacked by each kind of piece of color, indexed by piece
+	fn attack_map(board: &Board, color: Color) -> [BitBoard; 6] {
+		let occupied = board.occupied();
+		let mut attacks = [BitBoard::EMPTY; 6];
+
+		for &piece in &Piece::ALL {
+			for square in board.colors(color) & board.pieces(piece) {
+				attacks[piece as usize] |= Self::piece_attacks(piece, square, color, occupied);
+			}
+		}
+
+		attacks
+	}
+
+	//how many ranks the closest of pawns is ahead of square, from our point of view
+	fn ranks_ahead(&self, pawns: BitBoard, square: Square) -> Option<usize> {
+		let rank = square.rank().relative_to(self.color) as usize;
+
+		pawns.iter()
+			.map(|pawn| pawn.rank().relative_to(self.color) as usize)
+			.filter(|&pawn_rank| pawn_rank > rank)
+			.map(|pawn_rank| pawn_rank - rank)
+			.min()
+	}
+
+	fn piece_attacks(piece: Piece, square: Square, color: Color, occupied: BitBoard) -> BitBoard {
+		match piece {
+			Piece::Pawn => get_pawn_attacks(square, color),
+			Piece::Knight => get_knight_moves(square),
+			Piece::Bishop => get_bishop_moves(square, occupied),
+			Piece::Rook => get_rook_moves(square, occupied),
+			Piece::Queen => get_bishop_moves(square, occupied) | get_rook_moves(square, occupied),
+			Piece::King => get_king_moves(square)
+		}
+	}
+
+	//squares one rank further up the board for color
+	fn forward(squares: BitBoard, color: Color) -> BitBoard {
+		match color {
+			Color::White => BitBoard(squares.0 << 8),
+			Color::Black => BitBoard(squares.0 >> 8)
+		}
+	}
+
+	fn connected_pawns(&mut self, phase: i32) -> i32 {
 		let mut bonus = 0;
 		let our_pawns = self.board.colors(self.color) & self.board.pieces(Piece::Pawn);
 
@@ -108,7 +339,8 @@ impl Evaluator<'_> {
 			for supporting_location in get_pawn_attacks(pawn, !self.color) {
 				if !(supporting_location.bitboard() & our_pawns).is_empty() {
 					//we have one of our pawns on this square, supporting the checking pawn
-					bonus += CONNECTED_PASSED_PAWN.eval(phase);
+					bonus += self.params.connected_passed_pawn.eval(phase);
+					self.trace(|t| &mut t.connected_passed_pawn);
 				}
 			}
 		}
@@ -116,24 +348,25 @@ impl Evaluator<'_> {
 		bonus
 	}
 
-	fn virtual_mobility(&self, phase: i32) -> i32 {
+	fn virtual_mobility(&mut self, phase: i32) -> i32 {
 		let occupied = self.board.occupied();
 		let my_king = self.board.king(self.color);
 
 		let virtual_queen_moves = (get_bishop_moves(my_king, occupied) | get_rook_moves(my_king, occupied)) & !self.board.colors(self.color);
-		let mobility = virtual_queen_moves.len();
+		let mobility = virtual_queen_moves.len() as usize;
 
-		VIRTUAL_MOBILITY[mobility as usize].eval(phase)
+		self.trace(|t| &mut t.virtual_mobility[mobility]);
+		self.params.virtual_mobility[mobility].eval(phase)
 	}
 
-	fn get_mobility(&self, phase: i32) -> i32 {
+	fn get_mobility(&mut self, phase: i32) -> i32 {
 		let mut score = 0;
 		let our_pieces = self.board.colors(self.color);
 		let occupied = self.board.occupied();
 
 		for &piece in &Piece::ALL {
 			let our_this_piece = our_pieces & self.board.pieces(piece);
-			let mobility_weight = self.get_mobility_weight(piece);
+			let mobility_weight = Self::get_mobility_weight(self.params, piece);
 
 			//Sum up number of moves that our pieces have that can have, including loud moves.
 			for square in our_this_piece {
@@ -160,14 +393,16 @@ impl Evaluator<'_> {
 					}
 				}
 
-				score += mobility_weight[feasible_moves.len() as usize].eval(phase);
+				let mobility = feasible_moves.len() as usize;
+				score += mobility_weight[mobility].eval(phase);
+				self.trace(|t| &mut Self::get_mobility_trace(t, piece)[mobility]);
 			}
 		}
 
 		score
 	}
 
-	fn rook_files(&self, phase: i32) -> i32 {
+	fn rook_files(&mut self, phase: i32) -> i32 {
 		let mut score = 0;
 		let our_pieces = self.board.colors(self.color);
 		let all_pawns = self.board.pieces(Piece::Pawn);
@@ -180,17 +415,19 @@ impl Evaluator<'_> {
 			//check if there are no pawns on the line our rook is at. if so, it is on open file. if there are only enemy pawns on it, it is on a semi-opem file.
 			if (all_pawns & rook_file).is_empty() {
 				//it is on an open file
-				score += ROOK_OPEN_FILE_BONUS.eval(phase);
+				score += self.params.rook_open_file_bonus.eval(phase);
+				self.trace(|t| &mut t.rook_open_file_bonus);
 			} else if (our_pawns & rook_file).is_empty() {
 				//it is on a semi open file
-				score += ROOK_SEMI_FILE_BONUS.eval(phase);
+				score += self.params.rook_semi_file_bonus.eval(phase);
+				self.trace(|t| &mut t.rook_semi_file_bonus);
 			}
 		}
 
 		score
 	}
 
-	fn passed_pawns(&self, phase: i32) -> i32 {
+	fn passed_pawns(&mut self, phase: i32) -> i32 {
 		let mut score = 0;
 		let all_pawns = self.board.pieces(Piece::Pawn);
 		let our_pawns = all_pawns & self.board.colors(self.color);
@@ -214,14 +451,15 @@ impl Evaluator<'_> {
 			//check to see if these three BB files contain enemy pawns in them && and if this is not a pawn island
 			let passed = (enemy_pawns & block_mask).is_empty() && (our_pawns & get_between_rays(pawn, Square::new(pawn.file(), promo_rank))).is_empty();
 			if passed {
-				score += PASSED_PAWN_BONUS.eval(phase);
+				score += self.params.passed_pawn_bonus.eval(phase);
+				self.trace(|t| &mut t.passed_pawn_bonus);
 			}
 		}
 
 		score
 	}
 
-	fn pawn_island(&self, phase: i32) -> i32 {
+	fn pawn_island(&mut self, phase: i32) -> i32 {
 		let mut penalty = 0;
 		let all_pawns = self.board.pieces(Piece::Pawn);
 		let our_pawns = all_pawns & self.board.colors(self.color);
@@ -234,14 +472,15 @@ impl Evaluator<'_> {
 			//check if there are any of our pawns ahead of us, blocking the way
 			let is_island = !(our_pawns & block_mask).is_empty();
 			if is_island {
-				penalty += PAWN_ISLAND_PENALTY.eval(phase);
+				penalty += self.params.pawn_island_penalty.eval(phase);
+				self.trace(|t| &mut t.pawn_island_penalty);
 			}
 		}
 
 		penalty
 	}
 
-	fn isolated_pawn(&self, phase: i32) -> i32 {
+	fn isolated_pawn(&mut self, phase: i32) -> i32 {
 		let mut penalty = 0;
 		let all_pawns = self.board.pieces(Piece::Pawn);
 		let our_pawns = all_pawns & self.board.colors(self.color);
@@ -262,17 +501,19 @@ impl Evaluator<'_> {
 			//check to see if we have any supporting pawns on neighbouring files
 			let is_isolated = (our_pawns & block_mask).is_empty();
 			if is_isolated {
-				penalty += PAWN_ISOLATION_PENALTY.eval(phase);
+				penalty += self.params.pawn_isolation_penalty.eval(phase);
+				self.trace(|t| &mut t.pawn_isolation_penalty);
 			}
 		}
 
 		penalty
 	}
 
-	fn bishop_pair(&self, phase: i32) -> i32 {
+	fn bishop_pair(&mut self, phase: i32) -> i32 {
 		let mut score = 0;
 		if (self.board.pieces(Piece::Bishop) & self.board.colors(self.color)).len() >= 2 {
-			score += BISHOP_PAIR_BONUS.eval(phase);
+			score += self.params.bishop_pair_bonus.eval(phase);
+			self.trace(|t| &mut t.bishop_pair_bonus);
 		}
 
 		score
@@ -316,33 +557,77 @@ impl Evaluator<'_> {
 	const QUEEN_PHASE: i32 = 4;
 	const TOTAL_PIECE_PHASE: i32 = 24;
 	const ORACLE_SCALE: i32 = 100;
+
+	//KING SAFETY ATTACK UNITS, INDEXED BY PIECE
+	const ATTACKER_UNITS: [i32; 6] = [0, 2, 2, 3, 5, 0];
+	const SAFE_CHECK_UNITS: [i32; 6] = [0, 4, 3, 5, 6, 0];
+	const ZONE_ATTACK_UNITS: i32 = 1;
+	const WEAK_RING_UNITS: i32 = 1;
+	const KING_ATTACKERS_MIN: i32 = 2;
+
+	//PIECE VALUES FOR DECIDING IF AN ATTACKER IS WORTH LESS THAN ITS TARGET, INDEXED BY PIECE
+	const THREAT_VALUES: [i32; 6] = [1, 3, 3, 5, 9, 100];
+
+	//PAWNS FURTHER AHEAD OF THE KING THAN THIS DO NOT SHIELD IT OR STORM IT
+	const SHIELD_DISTANCE: usize = 3;
+	const STORM_DISTANCE: usize = 4;
+}
+
+//what the tuner needs besides the trace to rebuild an evaluation from the weights
+pub struct TraceInfo {
+	pub phase: i32,
+	pub scale: i32
+}
+
+pub fn evaluate(board: &Board, params: &EvalParams) -> i32 {
+	let (eval, _) = evaluate_white(board, params, None);
+
+	if board.side_to_move() == Color::White {
+		eval
+	} else {
+		-eval
+	}
+}
+
+//records the white relative coefficient of every weight into the trace, which does not depend on the weights themselves
+pub fn evaluate_traced(board: &Board, trace: &mut Trace) -> TraceInfo {
+	let (_, info) = evaluate_white(board, &EvalParams::DEFAULT, Some(trace));
+	info
 }
 
-pub fn evaluate(board: &Board) -> i32 {
+fn evaluate_white(board: &Board, params: &EvalParams, mut trace: Option<&mut Trace>) -> (i32, TraceInfo) {
 	let mut eval = 0;
 
-	let white_eval = Evaluator::new(board, Color::White);
-	let black_eval = Evaluator::new(board, Color::Black);
+	//built once here since both sides need both maps
+	let attacks = [Evaluator::attack_map(board, Color::White), Evaluator::attack_map(board, Color::Black)];
 
+	let mut white_eval = Evaluator::new(board, Color::White, params, &attacks, trace.as_deref_mut());
 	eval += white_eval.eval();
+
+	let mut black_eval = Evaluator::new(board, Color::Black, params, &attacks, trace.as_deref_mut());
 	eval -= black_eval.eval();
 
 	//load in extra calculations
-	let phase = white_eval.calculate_phase();
+	let phase = black_eval.calculate_phase();
 	if board.side_to_move() == Color::White {
-		eval += TEMPO.eval(phase);
+		eval += params.tempo.eval(phase);
 	} else {
-		eval -= TEMPO.eval(phase);
+		eval -= params.tempo.eval(phase);
+	}
+
+	if let Some(trace) = trace {
+		trace.tempo += if board.side_to_move() == Color::White { 1 } else { -1 };
 	}
 
+	let mut scale = 1;
 	if oracle_lookup(board) {
 		//scale eval down in the case of a known draw
 		eval /= Evaluator::ORACLE_SCALE;
+		scale = Evaluator::ORACLE_SCALE;
 	}
 
-	if board.side_to_move() == Color::White {
-		eval
-	} else {
-		-eval
-	}
+	(eval, TraceInfo {
+		phase,
+		scale
+	})
 }
\ No newline at end of file
@@ -1,5 +1,7 @@
 #[macro_use]
 pub mod score;
 pub mod eval_info;
+pub mod terms;
 pub mod evaluator;
-pub mod draw_oracle;
\ No newline at end of file
+pub mod draw_oracle;
+pub mod nnue;
\ No newline at end of file
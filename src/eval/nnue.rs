@@ -0,0 +1,152 @@
+use cozy_chess::*;
+
+use std::sync::Arc;
+
+/*
+(768 -> HIDDEN)x2 -> 1 perspective network with SCReLU activation
+File layout (little endian i16): feature weights [768][HIDDEN], feature bias [HIDDEN], output weights [2][HIDDEN], output bias
+https://github.com/jw1912/bullet
+*/
+
+pub const HIDDEN: usize = 256;
+
+const INPUTS: usize = 768;
+const QA: i32 = 255;
+const QB: i32 = 64;
+const SCALE: i32 = 400;
+
+pub struct Network {
+	feature_weights: Vec<[i16; HIDDEN]>,
+	feature_bias: [i16; HIDDEN],
+	output_weights: [[i16; HIDDEN]; 2],
+	output_bias: i16
+}
+
+#[derive(Clone, Copy)]
+#[repr(C, align(64))]
+pub struct Accumulator {
+	//indexed by perspective
+	vals: [[i16; HIDDEN]; 2]
+}
+
+fn feature_index(perspective: Color, color: Color, piece: Piece, square: Square) -> usize {
+	let square = if perspective == Color::White {
+		square as usize
+	} else {
+		//mirrors square
+		square as usize ^ 0x38
+	};
+
+	(color != perspective) as usize * 384 + piece as usize * 64 + square
+}
+
+fn screlu(x: i16) -> i32 {
+	let x = (x as i32).clamp(0, QA);
+	x * x
+}
+
+impl Network {
+	//shared between the search threads, and freed once no engine uses it anymore
+	pub fn load(path: &str) -> Result<Arc<Network>, String> {
+		let bytes = std::fs::read(path).map_err(|e| format!("could not read {}: {}", path, e))?;
+		let expected = (INPUTS * HIDDEN + HIDDEN + 2 * HIDDEN + 1) * 2;
+
+		//trainers may pad the file to a multiple of 64 bytes
+		if bytes.len() < expected || bytes.len() - expected >= 64 {
+			return Err(format!("{} is {} bytes, expected {} for a (768->{})x2->1 network", path, bytes.len(), expected, HIDDEN));
+		}
+
+		let mut values = bytes.chunks_exact(2).map(|pair| i16::from_le_bytes([pair[0], pair[1]]));
+		let mut network = Network {
+			feature_weights: vec![[0; HIDDEN]; INPUTS],
+			feature_bias: [0; HIDDEN],
+			output_weights: [[0; HIDDEN]; 2],
+			output_bias: 0
+		};
+
+		for row in network.feature_weights.iter_mut() {
+			row.iter_mut().for_each(|w| *w = values.next().unwrap());
+		}
+		network.feature_bias.iter_mut().for_each(|w| *w = values.next().unwrap());
+		for row in network.output_weights.iter_mut() {
+			row.iter_mut().for_each(|w| *w = values.next().unwrap());
+		}
+		network.output_bias = values.next().unwrap();
+
+		Ok(Arc::new(network))
+	}
+
+	//evaluation from the side to move's point of view
+	pub fn evaluate(&self, accumulator: &Accumulator, side_to_move: Color) -> i32 {
+		let us = &accumulator.vals[side_to_move as usize];
+		let them = &accumulator.vals[!side_to_move as usize];
+
+		let mut sum = 0;
+		for i in 0..HIDDEN {
+			sum += screlu(us[i]) * self.output_weights[0][i] as i32;
+			sum += screlu(them[i]) * self.output_weights[1][i] as i32;
+		}
+
+		(sum / QA + self.output_bias as i32) * SCALE / (QA * QB)
+	}
+}
+
+impl Accumulator {
+	pub fn new() -> Accumulator {
+		Accumulator {
+			vals: [[0; HIDDEN]; 2]
+		}
+	}
+
+	pub fn refresh(&mut self, network: &Network, board: &Board) {
+		self.vals = [network.feature_bias; 2];
+
+		for &color in &Color::ALL {
+			for &piece in &Piece::ALL {
+				for square in board.colored_pieces(color, piece) {
+					self.add(network, color, piece, square);
+				}
+			}
+		}
+	}
+
+	//applies the difference between two boards, which covers captures, promotions, castling and en passant alike
+	pub fn update(&mut self, network: &Network, old: &Board, new: &Board) {
+		for &color in &Color::ALL {
+			for &piece in &Piece::ALL {
+				let before = old.colored_pieces(color, piece);
+				let after = new.colored_pieces(color, piece);
+
+				for square in before & !after {
+					self.sub(network, color, piece, square);
+				}
+
+				for square in after & !before {
+					self.add(network, color, piece, square);
+				}
+			}
+		}
+	}
+
+	fn add(&mut self, network: &Network, color: Color, piece: Piece, square: Square) {
+		for &perspective in &Color::ALL {
+			let weights = &network.feature_weights[feature_index(perspective, color, piece, square)];
+			let vals = &mut self.vals[perspective as usize];
+
+			for i in 0..HIDDEN {
+				vals[i] += weights[i];
+			}
+		}
+	}
+
+	fn sub(&mut self, network: &Network, color: Color, piece: Piece, square: Square) {
+		for &perspective in &Color::ALL {
+			let weights = &network.feature_weights[feature_index(perspective, color, piece, square)];
+			let vals = &mut self.vals[perspective as usize];
+
+			for i in 0..HIDDEN {
+				vals[i] -= weights[i];
+			}
+		}
+	}
+}
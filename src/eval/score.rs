@@ -5,6 +5,7 @@ pub struct Eval {
 	pub mate: bool
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Score {
 	mg: i32,
 	eg: i32
@@ -33,6 +34,14 @@ impl Score {
 		}
 	}
 
+	pub const fn mg(&self) -> i32 {
+		self.mg
+	}
+
+	pub const fn eg(&self) -> i32 {
+		self.eg
+	}
+
 	pub fn eval(&self, phase: i32) -> i32 {
 		((self.mg * (Self::TOTAL_PHASE - phase)) + (self.eg * phase)) / Self::TOTAL_PHASE
 	}
@@ -45,6 +54,9 @@ impl Score {
 	//NUMBER TO BASE CHECKMATES OFF FROM
 	pub const CHECKMATE_BASE: i32 = 29500;
 
+	//SCORES BEYOND THIS ARE MATES, WHICH ARE CHECKMATE_BASE MINUS THE PLY
+	pub const MATE_THRESHOLD: i32 = Self::CHECKMATE_BASE - 1000;
+
 	//THE TOTAL PHASE FOR OUR TAPERED EVAL
-	const TOTAL_PHASE: i32 = 256;
+	pub const TOTAL_PHASE: i32 = 256;
 }
\ No newline at end of file
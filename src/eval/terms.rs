@@ -0,0 +1,172 @@
+use std::fs;
+use std::sync::Arc;
+
+use crate::eval::score::*;
+use crate::eval::eval_info::*;
+
+/*
+Every evaluation term in eval_info order
+EvalTerms<Score> holds the weights, EvalTerms<i32> holds how often each weight was applied (the tuner's coefficient trace)
+Parameter files hold one term per line, the const name followed by its mg eg pairs, e.g. "PAWN 71 96"
+Terms missing from a file keep their eval_info weights and # starts a comment
+*/
+
+macro_rules! term_type {
+	($t:ty) => { $t };
+	($t:ty, $len:expr) => { [$t; $len] };
+}
+
+macro_rules! term_slice {
+	($e:expr) => { std::slice::from_ref(&$e) };
+	($e:expr, $len:expr) => { &$e[..] };
+}
+
+macro_rules! term_slice_mut {
+	($e:expr) => { std::slice::from_mut(&mut $e) };
+	($e:expr, $len:expr) => { &mut $e[..] };
+}
+
+macro_rules! term_zero {
+	() => { 0 };
+	($len:expr) => { [0; $len] };
+}
+
+macro_rules! eval_terms {
+	($($field:ident: $name:ident $([$len:expr])?),* $(,)?) => {
+		pub struct EvalTerms<T> {
+			$(pub $field: term_type!(T $(, $len)?),)*
+		}
+
+		impl<T: Copy> Clone for EvalTerms<T> {
+			fn clone(&self) -> Self {
+				*self
+			}
+		}
+
+		impl<T: Copy> Copy for EvalTerms<T> {}
+
+		impl<T> EvalTerms<T> {
+			//visits every term in eval_info order with its const name
+			pub fn visit(&self, mut f: impl FnMut(&'static str, &[T])) {
+				$(f(stringify!($name), term_slice!(self.$field $(, $len)?));)*
+			}
+
+			pub fn visit_mut(&mut self, mut f: impl FnMut(&'static str, &mut [T])) {
+				$(f(stringify!($name), term_slice_mut!(self.$field $(, $len)?));)*
+			}
+		}
+
+		impl EvalTerms<Score> {
+			pub const DEFAULT: EvalTerms<Score> = EvalTerms {
+				$($field: $name,)*
+			};
+		}
+
+		impl EvalTerms<i32> {
+			pub const ZERO: EvalTerms<i32> = EvalTerms {
+				$($field: term_zero!($($len)?),)*
+			};
+		}
+	};
+}
+
+eval_terms! {
+	pawn: PAWN,
+	knight: KNIGHT,
+	bishop: BISHOP,
+	rook: ROOK,
+	queen: QUEEN,
+	tempo: TEMPO,
+	pawn_mobility: PAWN_MOBILITY[5],
+	knight_mobility: KNIGHT_MOBILITY[9],
+	bishop_mobility: BISHOP_MOBILITY[14],
+	rook_mobility: ROOK_MOBILITY[15],
+	queen_mobility: QUEEN_MOBILITY[28],
+	king_mobility: KING_MOBILITY[9],
+	virtual_mobility: VIRTUAL_MOBILITY[28],
+	bishop_pair_bonus: BISHOP_PAIR_BONUS,
+	passed_pawn_bonus: PASSED_PAWN_BONUS,
+	pawn_island_penalty: PAWN_ISLAND_PENALTY,
+	pawn_isolation_penalty: PAWN_ISOLATION_PENALTY,
+	rook_open_file_bonus: ROOK_OPEN_FILE_BONUS,
+	rook_semi_file_bonus: ROOK_SEMI_FILE_BONUS,
+	connected_passed_pawn: CONNECTED_PASSED_PAWN,
+	king_on_semi_open_file: KING_ON_SEMI_OPEN_FILE,
+	king_on_open_file: KING_ON_OPEN_FILE,
+	pawn_push_threat: PAWN_PUSH_THREAT,
+	threat_on_move: THREAT_ON_MOVE,
+	king_danger: KING_DANGER[40],
+	pawn_shield: PAWN_SHIELD[4],
+	pawn_storm: PAWN_STORM[4],
+	threat_by_pawn: THREAT_BY_PAWN[5],
+	threat_by_lesser: THREAT_BY_LESSER[5],
+	hanging: HANGING[5],
+	p: P[64],
+	n: N[64],
+	b: B[64],
+	r: R[64],
+	q: Q[64],
+	k: K[64],
+}
+
+pub type EvalParams = EvalTerms<Score>;
+pub type Trace = EvalTerms<i32>;
+
+impl EvalTerms<Score> {
+	//shared like networks, and freed once no engine uses them anymore
+	pub fn load(path: &str) -> Result<Arc<EvalParams>, String> {
+		let text = fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path, e))?;
+		let params = Self::parse(&text).map_err(|e| format!("{}: {}", path, e))?;
+
+		Ok(Arc::new(params))
+	}
+
+	pub fn parse(text: &str) -> Result<EvalParams, String> {
+		let mut params = Self::DEFAULT;
+
+		for (line_index, line) in text.lines().enumerate() {
+			let line = line.split('#').next().unwrap().trim();
+			if line.is_empty() {
+				continue;
+			}
+
+			let mut tokens = line.split_whitespace();
+			let name = tokens.next().unwrap();
+			let values = tokens.map(|token| token.trim_matches(',').parse::<i32>())
+				.collect::<Result<Vec<i32>, _>>()
+				.map_err(|e| format!("line {}: {}", line_index + 1, e))?;
+
+			let mut result = Err(format!("line {}: unknown term {}", line_index + 1, name));
+			params.visit_mut(|term, weights| {
+				if term != name {
+					return;
+				}
+
+				if values.len() != weights.len() * 2 {
+					result = Err(format!("line {}: {} takes {} values, got {}", line_index + 1, name, weights.len() * 2, values.len()));
+					return;
+				}
+
+				for (weight, pair) in weights.iter_mut().zip(values.chunks(2)) {
+					*weight = Score::new(pair[0], pair[1]);
+				}
+				result = Ok(());
+			});
+
+			result?;
+		}
+
+		Ok(params)
+	}
+
+	pub fn text(&self) -> String {
+		let mut text = String::new();
+
+		self.visit(|name, weights| {
+			let values: Vec<String> = weights.iter().map(|w| format!("{} {}", w.mg(), w.eg())).collect();
+			text += &format!("{} {}\n", name, values.join(" "));
+		});
+
+		text
+	}
+}
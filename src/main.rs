@@ -5,6 +5,8 @@ mod uci;
 mod search;
 mod eval;
 mod movegen;
+mod datagen;
+mod tune;
 
 use crate::uci::uci::*;
 use std::io;
@@ -1,7 +1,12 @@
 use cozy_chess::*;
 
+use std::sync::Arc;
+
 use crate::movegen::movegen::*;
 use crate::movegen::movesorter::*;
+use crate::eval::evaluator::*;
+use crate::eval::nnue::*;
+use crate::eval::terms::*;
 
 fn init_pawn_hash(board: Board) -> u64 {
 	let mut hash = 0u64;
@@ -63,7 +68,10 @@ pub struct BoardWrapper {
     pub board: Board,
     pub pawn_hash: u64,
     pub non_pawn_hash: [u64; 2],
-    pub material_hash: u64
+    pub material_hash: u64,
+    pub nnue: Option<Arc<Network>>,
+    pub eval_params: Arc<EvalParams>,
+    accumulator: Accumulator
 }
 
 impl BoardWrapper {
@@ -74,7 +82,10 @@ impl BoardWrapper {
             board: new_board.clone(),
             pawn_hash: init_pawn_hash(new_board.clone()),
             non_pawn_hash: init_non_pawn_hash(new_board.clone()),
-            material_hash: init_material_hash(new_board.clone())
+            material_hash: init_material_hash(new_board.clone()),
+            nnue: None,
+            eval_params: Arc::new(EvalParams::DEFAULT),
+            accumulator: Accumulator::new()
         }
     }
 
@@ -83,7 +94,10 @@ impl BoardWrapper {
             board: board,
             pawn_hash: self.pawn_hash,
             non_pawn_hash: self.non_pawn_hash,
-            material_hash: self.material_hash
+            material_hash: self.material_hash,
+            nnue: self.nnue.clone(),
+            eval_params: self.eval_params.clone(),
+            accumulator: self.accumulator
         }
     }
 
@@ -92,15 +106,43 @@ impl BoardWrapper {
             board: self.board.clone(),
             pawn_hash: self.pawn_hash,
             non_pawn_hash: self.non_pawn_hash,
-            material_hash: self.material_hash
+            material_hash: self.material_hash,
+            nnue: self.nnue.clone(),
+            eval_params: self.eval_params.clone(),
+            accumulator: self.accumulator
         }
     }
 
     pub fn update_fen(&mut self, fen: String) {
-		self.board = Board::from_fen(&*fen.trim(), false).unwrap();
+        self.set_board(Board::from_fen(fen.trim(), false).unwrap());
+    }
+
+    //replaces the board and rebuilds everything that is otherwise updated incrementally
+    pub fn set_board(&mut self, board: Board) {
+        self.board = board;
         self.pawn_hash = init_pawn_hash(self.board.clone());
         self.non_pawn_hash = init_non_pawn_hash(self.board.clone());
         self.material_hash = init_material_hash(self.board.clone());
+
+        if let Some(nnue) = &self.nnue {
+            self.accumulator.refresh(nnue, &self.board);
+        }
+    }
+
+    pub fn set_nnue(&mut self, nnue: Option<Arc<Network>>) {
+        self.nnue = nnue;
+
+        if let Some(nnue) = &self.nnue {
+            self.accumulator.refresh(nnue, &self.board);
+        }
+    }
+
+    //evaluates with the network when one is loaded, otherwise with the hand crafted evaluation
+    pub fn evaluate(&self) -> i32 {
+        match &self.nnue {
+            Some(nnue) => nnue.evaluate(&self.accumulator, self.board.side_to_move()),
+            None => evaluate(&self.board, &self.eval_params)
+        }
     }
 
     pub fn null_move(&self) -> BoardWrapper {
@@ -191,7 +233,13 @@ impl BoardWrapper {
             self.non_pawn_hash[us as usize] ^= Self::BOARD_BY_PIECE_KEYS[piece_from.unwrap() as usize][mv.to as usize];
         }
 
-        self.board.play_unchecked(mv);
+        if let Some(nnue) = &self.nnue {
+            let old_board = self.board.clone();
+            self.board.play_unchecked(mv);
+            self.accumulator.update(nnue, &old_board, &self.board);
+        } else {
+            self.board.play_unchecked(mv);
+        }
     }
 }
 
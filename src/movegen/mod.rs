@@ -1,4 +1,5 @@
 pub mod movegen;
 pub mod movesorter;
+pub mod movepicker;
 pub mod see;
 pub mod boardwrapper;
\ No newline at end of file
@@ -29,9 +29,10 @@ impl SortedMove {
 		}
 	}
 
-	pub fn insert_history(&mut self, move_sorter: &mut MoveSorter, depth: i32) {
-		if self.movetype == MoveType::Quiet {
-			move_sorter.add_history(self.mv, depth);
+	pub fn insert_history(&mut self, move_sorter: &mut MoveSorter, board: &Board, conts: &[Option<PieceTo>; 2], depth: i32) {
+		match self.movetype {
+			MoveType::Quiet => move_sorter.update_history(self.mv, board, conts, depth * depth + 10),
+			MoveType::Loud => move_sorter.update_capture_history(self.mv, board, depth * depth + 10)
 		}
 	}
 
@@ -41,9 +42,10 @@ impl SortedMove {
 		}
 	}
 
-	pub fn decay_history(&mut self, move_sorter: &mut MoveSorter, depth: i32) {
-		if self.movetype == MoveType::Quiet {
-			move_sorter.decay_history(self.mv, depth);
+	pub fn decay_history(&mut self, move_sorter: &mut MoveSorter, board: &Board, conts: &[Option<PieceTo>; 2], depth: i32) {
+		match self.movetype {
+			MoveType::Quiet => move_sorter.update_history(self.mv, board, conts, -depth * depth),
+			MoveType::Loud => move_sorter.update_capture_history(self.mv, board, -depth * depth)
 		}
 	}
 }
@@ -59,58 +61,4 @@ impl MoveGen {
 			sorter: MoveSorter::new()
 		}
 	}
-
-	pub fn move_gen(&mut self, board: &Board, tt_move: Option<Move>, ply: i32, skip_hash: bool, last_move: Option<Move>) -> Vec<SortedMove> {
-		let mut move_list: Vec<SortedMove> = Vec::with_capacity(64);
-		let color = board.side_to_move();
-		let their_pieces = board.colors(!color);
-
-		//capture move
-		board.generate_moves(|moves| {
-			let mut capture_moves = moves;
-			capture_moves.to &= their_pieces;
-			for mv in capture_moves {
-				if Some(mv) == tt_move && skip_hash {
-					continue;
-				}
-				move_list.push(SortedMove::new(mv, 0, MoveType::Loud));
-			}
-			false
-		});
-
-		//quiet move
-		board.generate_moves(|moves| {
-			let mut quiet_moves = moves;
-			quiet_moves.to &= !their_pieces;
-			for mv in quiet_moves {
-				if Some(mv) == tt_move && skip_hash {
-					continue;
-				}
-				move_list.push(SortedMove::new(mv, 0, MoveType::Quiet));
-			}
-			false
-		});
-
-		self.sorter.sort(&mut move_list, tt_move, board, ply, last_move);
-
-		move_list
-	}
-
-	pub fn qmove_gen(&mut self, board: &Board, tt_move: Option<Move>, ply: i32) -> Vec<SortedMove> {
-		let mut move_list: Vec<SortedMove> = Vec::with_capacity(64);
-		let color = board.side_to_move();
-		let their_pieces = board.colors(!color);
-		board.generate_moves(|moves| {
-			let mut capture_moves = moves;
-			capture_moves.to &= their_pieces;
-			for mv in capture_moves {
-				move_list.push(SortedMove::new(mv, 0, MoveType::Loud));
-			}
-			false
-		});
-
-		self.sorter.sort(&mut move_list, tt_move, board, ply, None);
-
-		move_list
-	}
 }
\ No newline at end of file
@@ -0,0 +1,295 @@
+use cozy_chess::*;
+use crate::movegen::movegen::*;
+use crate::movegen::movesorter::*;
+
+/*
+Staged move picker, moves are handed out one at a time so a cutoff early on skips the work for the rest
+Order: TT move, good captures and queen promotions, killers, countermove, quiets, bad captures
+In quiescence: TT move, good captures and optionally quiet checks, or every evasion when in check
+Each stage is scored when it is reached and picked from with selection sort
+The move buffer is owned by the searcher and reused for every node at the same ply
+*/
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Stage {
+	TTMove,
+	Generate,
+	GoodNoisy,
+	Killers,
+	Countermove,
+	ScoreQuiets,
+	Quiets,
+	BadNoisy,
+	QuietChecks,
+	Done
+}
+
+pub struct MovePicker {
+	stage: Stage,
+	quiescence: bool,
+	checks: bool,
+	tt_move: Option<Move>,
+	killers: [Option<Move>; 2],
+	killer_index: usize,
+	countermove: Option<Move>,
+	last_move: Option<Move>,
+	conts: [Option<PieceTo>; 2],
+	ply: i32,
+	//noisy moves are kept in moves[..noisy_end] and quiets after them
+	moves: Vec<SortedMove>,
+	noisy_end: usize,
+	index: usize,
+	bad_index: usize,
+	move_count: usize
+}
+
+impl MovePicker {
+	pub fn new(buffer: Vec<SortedMove>, board: &Board, tt_move: Option<Move>, ply: i32, last_move: Option<Move>, conts: [Option<PieceTo>; 2]) -> MovePicker {
+		let mut moves = buffer;
+		moves.clear();
+
+		MovePicker {
+			stage: Stage::TTMove,
+			quiescence: false,
+			checks: false,
+			//a hash collision can hand us a move that is not legal here
+			tt_move: tt_move.filter(|&mv| board.is_legal(mv)),
+			killers: [None; 2],
+			killer_index: 0,
+			countermove: None,
+			last_move,
+			conts,
+			ply,
+			moves,
+			noisy_end: 0,
+			index: 0,
+			bad_index: 0,
+			move_count: 0
+		}
+	}
+
+	//only the TT move if it is a capture, captures that do not lose material and quiet checks if asked for
+	//in check every evasion is needed to tell if it is mate, so nothing is left out
+	pub fn new_quiescence(buffer: Vec<SortedMove>, board: &Board, tt_move: Option<Move>, ply: i32, checks: bool) -> MovePicker {
+		if !board.checkers().is_empty() {
+			return Self::new(buffer, board, tt_move, ply, None, [None; 2]);
+		}
+
+		let mut picker = Self::new(buffer, board, tt_move.filter(|&mv| Self::is_capture(board, mv)), ply, None, [None; 2]);
+		picker.quiescence = true;
+		picker.checks = checks;
+
+		picker
+	}
+
+	//hands the buffer back to the searcher
+	pub fn into_buffer(self) -> Vec<SortedMove> {
+		self.moves
+	}
+
+	pub fn tt_move(&self) -> Option<Move> {
+		self.tt_move
+	}
+
+	//legal moves other than the TT move, known once the picker is past the TT move
+	pub fn move_count(&self) -> usize {
+		self.move_count
+	}
+
+	pub fn next(&mut self, sorter: &mut MoveSorter, board: &Board) -> Option<SortedMove> {
+		loop {
+			match self.stage {
+				Stage::TTMove => {
+					self.stage = Stage::Generate;
+
+					if let Some(mv) = self.tt_move {
+						return Some(SortedMove::new(mv, MoveSorter::HASHMOVE_SCORE, Self::movetype(board, mv)));
+					}
+				},
+				Stage::Generate => {
+					self.generate(board);
+
+					for i in 0..self.noisy_end {
+						sorter.score(&mut self.moves[i], board, &self.conts);
+					}
+
+					self.index = 0;
+					self.stage = Stage::GoodNoisy;
+				},
+				Stage::GoodNoisy => {
+					if self.index < self.noisy_end {
+						let best = self.select(self.index, self.noisy_end);
+
+						if self.moves[best].importance >= 0 {
+							self.moves.swap(self.index, best);
+							self.index += 1;
+
+							return Some(self.moves[self.index - 1].clone());
+						}
+					}
+
+					//everything left over loses material
+					self.bad_index = self.index;
+					self.stage = if self.quiescence {
+						self.index = self.noisy_end;
+						if self.checks { Stage::QuietChecks } else { Stage::Done }
+					} else {
+						self.killers = sorter.get_killers(board, self.ply);
+						Stage::Killers
+					};
+				},
+				Stage::Killers => {
+					if self.killer_index >= self.killers.len() {
+						self.countermove = sorter.get_countermove(self.last_move).filter(|&mv| !self.killers.contains(&Some(mv)));
+						self.stage = Stage::Countermove;
+						continue;
+					}
+
+					let killer = self.killers[self.killer_index];
+					self.killer_index += 1;
+
+					if let Some(mv) = killer.filter(|&mv| self.is_refutation(board, mv)) {
+						let mut sm = SortedMove::new(mv, 0, MoveType::Quiet);
+						sorter.score(&mut sm, board, &self.conts);
+
+						sm.is_killer = true;
+						sm.is_countermove = sorter.get_countermove(self.last_move) == Some(mv);
+						sm.importance = MoveSorter::KILLER_QUIET + if sm.is_countermove { MoveSorter::COUNTER_QUIET } else { 0 };
+
+						return Some(sm);
+					}
+				},
+				Stage::Countermove => {
+					self.stage = Stage::ScoreQuiets;
+
+					if let Some(mv) = self.countermove.filter(|&mv| self.is_refutation(board, mv)) {
+						let mut sm = SortedMove::new(mv, 0, MoveType::Quiet);
+						sorter.score(&mut sm, board, &self.conts);
+
+						sm.is_countermove = true;
+						sm.importance = MoveSorter::COUNTER_QUIET;
+
+						return Some(sm);
+					}
+				},
+				Stage::ScoreQuiets => {
+					let mut i = self.noisy_end;
+					while i < self.moves.len() {
+						let mv = Some(self.moves[i].mv);
+
+						//already handed out as a killer or countermove
+						if self.killers.contains(&mv) || self.countermove == mv {
+							self.moves.swap_remove(i);
+							continue;
+						}
+
+						sorter.score(&mut self.moves[i], board, &self.conts);
+						i += 1;
+					}
+
+					self.index = self.noisy_end;
+					self.stage = Stage::Quiets;
+				},
+				Stage::Quiets => {
+					if self.index < self.moves.len() {
+						let best = self.select(self.index, self.moves.len());
+						self.moves.swap(self.index, best);
+						self.index += 1;
+
+						return Some(self.moves[self.index - 1].clone());
+					}
+
+					self.stage = Stage::BadNoisy;
+				},
+				Stage::BadNoisy => {
+					if self.bad_index < self.noisy_end {
+						let best = self.select(self.bad_index, self.noisy_end);
+						self.moves.swap(self.bad_index, best);
+						self.bad_index += 1;
+
+						return Some(self.moves[self.bad_index - 1].clone());
+					}
+
+					self.stage = Stage::Done;
+				},
+				Stage::QuietChecks => {
+					while self.index < self.moves.len() {
+						let sm = self.moves[self.index].clone();
+						self.index += 1;
+
+						let mut child = board.clone();
+						child.play_unchecked(sm.mv);
+						if !child.checkers().is_empty() {
+							return Some(sm);
+						}
+					}
+
+					self.stage = Stage::Done;
+				},
+				Stage::Done => {
+					return None;
+				}
+			}
+		}
+	}
+
+	//one pass of move generation, partitioned into noisy moves followed by quiets
+	fn generate(&mut self, board: &Board) {
+		let captures_only = self.quiescence && !self.checks;
+		let tt_move = self.tt_move;
+		let moves = &mut self.moves;
+
+		board.generate_moves(|piece_moves| {
+			for mv in piece_moves {
+				if Some(mv) != tt_move && (!captures_only || Self::is_capture(board, mv)) {
+					moves.push(SortedMove::new(mv, 0, Self::movetype(board, mv)));
+				}
+			}
+			false
+		});
+
+		self.move_count = self.moves.len();
+
+		self.noisy_end = 0;
+		for i in 0..self.moves.len() {
+			if Self::is_noisy(board, self.moves[i].mv) {
+				self.moves.swap(i, self.noisy_end);
+				self.noisy_end += 1;
+			}
+		}
+	}
+
+	fn select(&self, start: usize, end: usize) -> usize {
+		let mut best = start;
+		for i in start + 1..end {
+			if self.moves[i].importance > self.moves[best].importance {
+				best = i;
+			}
+		}
+
+		best
+	}
+
+	//killers and countermoves come from other positions, so they are only used if they are legal quiets here
+	fn is_refutation(&self, board: &Board, mv: Move) -> bool {
+		Some(mv) != self.tt_move && !Self::is_noisy(board, mv) && board.is_legal(mv)
+	}
+
+	//en passant counts, its target square is empty
+	fn is_capture(board: &Board, mv: Move) -> bool {
+		!(mv.to.bitboard() & board.colors(!board.side_to_move())).is_empty()
+		|| (board.piece_on(mv.from) == Some(Piece::Pawn) && mv.from.file() != mv.to.file())
+	}
+
+	fn is_noisy(board: &Board, mv: Move) -> bool {
+		Self::is_capture(board, mv) || mv.promotion == Some(Piece::Queen)
+	}
+
+	fn movetype(board: &Board, mv: Move) -> MoveType {
+		if Self::is_capture(board, mv) {
+			MoveType::Loud
+		} else {
+			MoveType::Quiet
+		}
+	}
+}
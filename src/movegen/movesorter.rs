@@ -2,6 +2,8 @@ use cozy_chess::*;
 use crate::movegen::movegen::*;
 use crate::movegen::see::*;
 use crate::movegen::boardwrapper::*;
+use crate::search::tunables::*;
+use crate::eval::terms::*;
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum MoveType {
@@ -9,10 +11,35 @@ pub enum MoveType {
 	Quiet
 }
 
+//the side, piece and destination of a played move, which key the continuation histories
+#[derive(Clone, Copy, Debug)]
+pub struct PieceTo {
+	pub color: Color,
+	pub piece: Piece,
+	pub to: Square
+}
+
+impl PieceTo {
+	pub fn new(board: &Board, mv: Move) -> PieceTo {
+		PieceTo {
+			color: board.side_to_move(),
+			piece: board.piece_on(mv.from).unwrap(),
+			to: mv.to
+		}
+	}
+}
+
+//indexed by [previous move side, piece and destination][piece][to], boxed as it is too large for the stack
+type ContinuationHistory = Box<[[[i32; 64]; 6]]>;
+
 #[derive(Clone)]
 pub struct MoveSorter {
 	killer_table: [[[Option<Move>; 2]; 100]; 2],
-	history_table: [[i32; 64]; 64],
+	history_table: [[[i32; 64]; 64]; 2],
+	piece_to_history: [[[i32; 64]; 6]; 2],
+	//1 ply and 2 ply continuation histories
+	continuation_history: [ContinuationHistory; 2],
+	capture_history: [[[[i32; 6]; 64]; 6]; 2],
 	countermove_table: [[Option<Move>; 64]; 64],
 	pawn_corrhist: [[f32; Self::CORRHIST_SIZE]; 2],
 	non_pawn_corrhist: [[f32; Self::CORRHIST_SIZE]; 2],
@@ -24,7 +51,10 @@ impl MoveSorter {
 	pub fn new () -> MoveSorter {
 		MoveSorter {
 			killer_table: [[[None; 2]; 100]; 2],
-			history_table: [[0; 64]; 64],
+			history_table: [[[0; 64]; 64]; 2],
+			piece_to_history: [[[0; 64]; 6]; 2],
+			continuation_history: [vec![[[0; 64]; 6]; 2 * 6 * 64].into_boxed_slice(), vec![[[0; 64]; 6]; 2 * 6 * 64].into_boxed_slice()],
+			capture_history: [[[[0; 6]; 64]; 6]; 2],
 			countermove_table: [[None; 64]; 64],
 			pawn_corrhist: [[0.0; Self::CORRHIST_SIZE]; 2],
 			non_pawn_corrhist: [[0.0; Self::CORRHIST_SIZE]; 2],
@@ -33,77 +63,74 @@ impl MoveSorter {
 		}
 	}
 
-	pub fn sort(&mut self, move_list: &mut Vec<SortedMove>, tt_move: Option<Move>, board: &Board, ply: i32, last_move: Option<Move>) {
-		for i in 0..move_list.len() {
-			let mv_info = &mut move_list[i];
+	//orders within a stage, killers and the countermove have stages of their own
+	pub fn score(&mut self, mv_info: &mut SortedMove, board: &Board, conts: &[Option<PieceTo>; 2]) {
+		let mut base = 0;
+		let mut increment = 0;
+
+		if mv_info.movetype == MoveType::Loud {
+			let capture_score = self.see.see(board, mv_info.mv);
+
+			base = if capture_score > 0 {
+				Self::WINNING_CAPTURE
+			} else if capture_score == 0 {
+				Self::NEUTRAL_CAPTURE
+			} else {
+				Self::LOSING_CAPTURE
+			};
+
+			let history = self.get_capture_history(mv_info.mv, board);
+			increment = capture_score + history / Self::CAPTURE_HISTORY_DIVISOR;
+			mv_info.history = history;
+		}
 
-			if tt_move != None {
-				if Some(mv_info.mv) == tt_move {
-					mv_info.importance = Self::HASHMOVE_SCORE;
-				}
-			}
+		if mv_info.movetype == MoveType::Quiet {
+			base = Self::QUIET_MOVE;
 
-			let is_tt = mv_info.importance == Self::HASHMOVE_SCORE;
+			mv_info.history = self.get_history(mv_info.mv, board, conts);
+			increment = mv_info.history;
+		}
 
-			if !is_tt {
-				let is_promo = mv_info.mv.promotion != None;
-				let mut base = 0;
-				let mut increment = 0;
+		if let Some(promotion) = mv_info.mv.promotion {
+			base = if promotion == Piece::Queen { 
+				Self::PROMO
+			} else { 
+				Self::UNDER_PROMO
+			};
+		}
 
-				if mv_info.movetype == MoveType::Loud {
-					let capture_score = self.see.see(board, mv_info.mv);
+		mv_info.importance = base + increment;
+	}
 
-					base = if capture_score > 0 {
-						Self::WINNING_CAPTURE
-					} else if capture_score == 0{
-						Self::NEUTRAL_CAPTURE
-					} else {
-						Self::LOSING_CAPTURE
-					};
+	pub fn see(&mut self, board: &Board, mv: Move) -> i32 {
+		self.see.see(board, mv)
+	}
 
-					increment = capture_score;
-				}
-	
-				if mv_info.movetype == MoveType::Quiet {
-					base = Self::QUIET_MOVE;
-	
-					mv_info.is_killer = self.is_killer(mv_info.mv, board, ply);
-					mv_info.is_countermove = self.is_countermove(mv_info.mv, last_move);
-	
-					if mv_info.is_killer || mv_info.is_countermove {
-						base = 0;
-
-						base += if mv_info.is_killer {
-							Self::KILLER_QUIET
-						} else {
-							0
-						};
-
-						base += if mv_info.is_countermove {
-							Self::COUNTER_QUIET
-						} else {
-							0
-						};
-					} else {
-						let history = self.get_history(mv_info.mv);
-						increment = history;
-						mv_info.history = history;
-					}
-				}
-	
-				if is_promo {
-					base = if mv_info.mv.promotion.unwrap() == Piece::Queen { 
-						Self::PROMO
-					} else { 
-						Self::UNDER_PROMO
-					};
-				}
+	pub fn see_ge(&self, board: &Board, mv: Move, threshold: i32) -> bool {
+		self.see.see_ge(board, mv, threshold)
+	}
 
-				mv_info.importance = base + increment;
-			}
+	pub fn piece_value(&self, piece: Piece) -> i32 {
+		self.see.piece_value(piece)
+	}
+
+	pub fn set_eval_params(&mut self, params: &EvalParams) {
+		self.see.set_eval_params(params);
+	}
+
+	pub fn get_killers(&self, board: &Board, ply: i32) -> [Option<Move>; 2] {
+		if ply < 100 {
+			return self.killer_table[board.side_to_move() as usize][ply as usize];
 		}
 
-		move_list.sort_by(|x, z| z.importance.cmp(&x.importance));
+		[None; 2]
+	}
+
+	pub fn get_countermove(&self, last_move: Option<Move>) -> Option<Move> {
+		match last_move {
+			Some(last_move) => self.countermove_table[last_move.from as usize][last_move.to as usize],
+			None => None
+		}
 	}
 
 	pub fn add_killer(&mut self, mv: Move, ply: i32, board: &Board) {
@@ -116,28 +143,44 @@ impl MoveSorter {
 		}
 	}
 
-	pub fn add_history(&mut self, mv: Move, depth: i32) {
-		let history = self.history_table[mv.from as usize][mv.to as usize];
-		let change = depth * depth + 10;
+	//quiet histories: butterfly and piece-to by side, and the continuations of the last two moves
+	pub fn update_history(&mut self, mv: Move, board: &Board, conts: &[Option<PieceTo>; 2], bonus: i32) {
+		let color = board.side_to_move() as usize;
+		let piece = board.piece_on(mv.from).unwrap() as usize;
+
+		Self::apply_bonus(&mut self.history_table[color][mv.from as usize][mv.to as usize], bonus);
+		Self::apply_bonus(&mut self.piece_to_history[color][piece][mv.to as usize], bonus);
 
-		if !change.checked_mul(history).is_none() {
-			self.history_table[mv.from as usize][mv.to as usize] += change - change * history / Self::HISTORY_MAX; //add quiet score into history table based on from and to squares
+		for (table, cont) in self.continuation_history.iter_mut().zip(conts) {
+			if let Some(prev) = cont {
+				Self::apply_bonus(&mut table[Self::continuation_index(prev)][piece][mv.to as usize], bonus);
+			}
 		}
 	}
 
-	pub fn add_countermove(&mut self, mv: Move, last_move: Move) {
-		self.countermove_table[last_move.from as usize][last_move.to as usize] = Some(mv);
-	}
+	pub fn update_capture_history(&mut self, mv: Move, board: &Board, bonus: i32) {
+		let color = board.side_to_move() as usize;
+		let piece = board.piece_on(mv.from).unwrap() as usize;
+		let captured = board.piece_on(mv.to).unwrap_or(Piece::Pawn) as usize; //en passant lands on an empty square
 
-	pub fn decay_history(&mut self, mv: Move, depth: i32) {
-		let history = self.history_table[mv.from as usize][mv.to as usize];
-		let change = depth * depth;
+		Self::apply_bonus(&mut self.capture_history[color][piece][mv.to as usize][captured], bonus);
+	}
 
-		if !change.checked_mul(history).is_none() {
-			self.history_table[mv.from as usize][mv.to as usize] -= change + change * history / Self::HISTORY_MAX; //decay quiet score into history table based on from and to squares
+	//history gravity, entries saturate towards HISTORY_MAX
+	fn apply_bonus(entry: &mut i32, bonus: i32) {
+		if bonus.checked_mul(*entry).is_some() {
+			*entry += bonus - bonus.abs() * *entry / Self::HISTORY_MAX;
 		}
 	}
 
+	fn continuation_index(prev: &PieceTo) -> usize {
+		(prev.color as usize * 6 + prev.piece as usize) * 64 + prev.to as usize
+	}
+
+	pub fn add_countermove(&mut self, mv: Move, last_move: Move) {
+		self.countermove_table[last_move.from as usize][last_move.to as usize] = Some(mv);
+	}
+
 	fn is_killer(&self, mv: Move, board: &Board, ply: i32) -> bool {
 		if ply < 100 {
 			let color = board.side_to_move();
@@ -161,8 +204,8 @@ impl MoveSorter {
 	
 		let entry = &mut self.material_corrhist[side][idx];
 	
-		let weight = f32::min(depth as f32 * depth as f32 + 2.0, 62.0) / 596.0;
-		*entry = *entry * (1.0 - weight) + ((best_alpha - static_eval) as f32).clamp(-81.0, 81.0) * 280.0 * weight;
+		let weight = f32::min(depth as f32 * depth as f32 + 2.0, 62.0) / corrhist_weight_divisor() as f32;
+		*entry = *entry * (1.0 - weight) + Self::corrhist_error(best_alpha, static_eval) * weight;
 	}
 
 	pub fn add_pawn_corrhist(&mut self, boardwrapper: &BoardWrapper, depth: i32, best_alpha: i32, static_eval: i32) {
@@ -171,8 +214,8 @@ impl MoveSorter {
 	
 		let entry = &mut self.pawn_corrhist[side][idx];
 	
-		let weight = f32::min(depth as f32 * depth as f32 + 2.0, 62.0) / 596.0;
-		*entry = *entry * (1.0 - weight) + ((best_alpha - static_eval) as f32).clamp(-81.0, 81.0) * 280.0 * weight;
+		let weight = f32::min(depth as f32 * depth as f32 + 2.0, 62.0) / corrhist_weight_divisor() as f32;
+		*entry = *entry * (1.0 - weight) + Self::corrhist_error(best_alpha, static_eval) * weight;
 	}
 
 	pub fn add_non_pawn_corrhist(&mut self, boardwrapper: &BoardWrapper, depth: i32, best_alpha: i32, static_eval: i32) {
@@ -180,65 +223,81 @@ impl MoveSorter {
 		let idx_black = (boardwrapper.non_pawn_hash[Color::Black as usize] % Self::CORRHIST_SIZE as u64) as usize;
 		let side_to_move = boardwrapper.board.side_to_move() as usize;
 	
-		let weight = f32::min(depth as f32 * depth as f32 + 2.0, 62.0) / 596.0;
+		let weight = f32::min(depth as f32 * depth as f32 + 2.0, 62.0) / corrhist_weight_divisor() as f32;
 
 		let entry_white = &mut self.non_pawn_corrhist[side_to_move][idx_white];
-		*entry_white = *entry_white * (1.0 - weight) + ((best_alpha - static_eval) as f32).clamp(-81.0, 81.0) * 280.0 * weight;
+		*entry_white = *entry_white * (1.0 - weight) + Self::corrhist_error(best_alpha, static_eval) * weight;
 
 		let entry_black = &mut self.non_pawn_corrhist[side_to_move][idx_black];	
-		*entry_black = *entry_black * (1.0 - weight) + ((best_alpha - static_eval) as f32).clamp(-81.0, 81.0) * 280.0 * weight;
+		*entry_black = *entry_black * (1.0 - weight) + Self::corrhist_error(best_alpha, static_eval) * weight;
+	}
+
+	fn corrhist_error(best_alpha: i32, static_eval: i32) -> f32 {
+		let clamp = corrhist_error_clamp() as f32;
+		((best_alpha - static_eval) as f32).clamp(-clamp, clamp) * corrhist_update_scale() as f32
 	}
 
 	pub fn read_material_corrhist(&mut self, boardwrapper: &BoardWrapper) -> f32 {
 		let material_hist = self.material_corrhist[boardwrapper.board.side_to_move() as usize][(boardwrapper.material_hash % Self::CORRHIST_SIZE as u64) as usize];
-		material_hist / 289.0
+		material_hist / material_corrhist_divisor() as f32
 	}
 
 	pub fn read_pawn_corrhist(&mut self, boardwrapper: &BoardWrapper) -> f32 {
 		let pawn_hist = self.pawn_corrhist[boardwrapper.board.side_to_move() as usize][(boardwrapper.pawn_hash % Self::CORRHIST_SIZE as u64) as usize];
-		pawn_hist / 198.0
+		pawn_hist / pawn_corrhist_divisor() as f32
 	}
 
 	pub fn read_non_pawn_corrhist(&mut self, boardwrapper: &BoardWrapper) -> f32 {
 		let side_to_move = boardwrapper.board.side_to_move() as usize;
 		let idx_white = (boardwrapper.non_pawn_hash[Color::White as usize] % Self::CORRHIST_SIZE as u64) as usize;
 		let idx_black = (boardwrapper.non_pawn_hash[Color::Black as usize] % Self::CORRHIST_SIZE as u64) as usize;
-		let non_pawn_hist_white = self.non_pawn_corrhist[side_to_move][idx_white] / 202.0;
-		let non_pawn_hist_black = self.non_pawn_corrhist[side_to_move][idx_black] / 202.0;
+		let non_pawn_hist_white = self.non_pawn_corrhist[side_to_move][idx_white] / non_pawn_corrhist_divisor() as f32;
+		let non_pawn_hist_black = self.non_pawn_corrhist[side_to_move][idx_black] / non_pawn_corrhist_divisor() as f32;
 
 		non_pawn_hist_white + non_pawn_hist_black
 	}
 
-	fn is_countermove(&self, mv: Move, last_move: Option<Move>) -> bool {
-		if last_move.is_none() {
-			return false;
+	fn get_history(&self, mv: Move, board: &Board, conts: &[Option<PieceTo>; 2]) -> i32 {
+		let color = board.side_to_move() as usize;
+		let piece = board.piece_on(mv.from).unwrap() as usize;
+		let mut history = self.history_table[color][mv.from as usize][mv.to as usize] + self.piece_to_history[color][piece][mv.to as usize];
+
+		for (table, cont) in self.continuation_history.iter().zip(conts) {
+			if let Some(prev) = cont {
+				history += table[Self::continuation_index(prev)][piece][mv.to as usize];
+			}
 		}
 
-		return self.countermove_table[last_move.unwrap().from as usize][last_move.unwrap().to as usize] == Some(mv);
+		history
 	}
 
-	fn get_history(&self, mv: Move) -> i32 {
-		return self.history_table[mv.from as usize][mv.to as usize];
+	fn get_capture_history(&self, mv: Move, board: &Board) -> i32 {
+		let color = board.side_to_move() as usize;
+		let piece = board.piece_on(mv.from).unwrap() as usize;
+		let captured = board.piece_on(mv.to).unwrap_or(Piece::Pawn) as usize; //en passant lands on an empty square
+
+		self.capture_history[color][piece][mv.to as usize][captured]
 	}
 }
 
 impl MoveSorter {
-	const HASHMOVE_SCORE: i32 = 1000000;
+	pub const HASHMOVE_SCORE: i32 = 1000000;
 
 	const PROMO: i32 = 50000;
 	const WINNING_CAPTURE: i32 = 50000;
 
 	const NEUTRAL_CAPTURE: i32 = 30000;
 
-	const KILLER_QUIET: i32 = 15000;
-	const COUNTER_QUIET: i32 = 10000;
+	pub const KILLER_QUIET: i32 = 15000;
+	pub const COUNTER_QUIET: i32 = 10000;
 	const QUIET_MOVE: i32 = 0;
 
 	const LOSING_CAPTURE: i32 = -50000;
 	const UNDER_PROMO: i32 = -50000;
 
 	const HISTORY_MAX: i32 = 2000;
+	const CAPTURE_HISTORY_DIVISOR: i32 = 8;
 	const CORRHIST_SIZE: usize = 16384;
 }
-//Ranking: TT, Promo, Good Loud Moves (further specifity by SEE), Best Quiets (further specifity by history), Quiets (furhter specifity by history), Bad Loud Moves = Underpromo
-//TT will have no specifity, Promos have no specifity
\ No newline at end of file
+//Ranking: TT, Promo, Good Loud Moves (further specifity by SEE), Killers, Countermove, Quiets (furhter specifity by history), Bad Loud Moves = Underpromo
+//TT will have no specifity, Promos have no specifity, the stages themselves are walked by the MovePicker
\ No newline at end of file
@@ -1,96 +1,215 @@
 use cozy_chess::*;
 
+use crate::eval::terms::*;
+
 /*
 Special thanks to Malarksist and Pali from Openbench!
 https://www.chessprogramming.org/SEE_-_The_Swap_Algorithm
+Handles en passant and promotions (including pawns recapturing onto the last rank), re-scans sliders after every capture for x-rays, and keeps pinned pieces off squares outside their pin
+Piece values are the midgame piece weights of the active eval params, so the exchange is counted in the same units as the evaluation
 */
 
-
 #[derive(Clone)]
 pub struct See {
-	gains: [i32; 32]
+	gains: [i32; 32],
+	values: [i32; 6]
 }
 
 impl See {
 	pub fn new() -> See {
 		See {
-			gains: [0_i32; 32]
+			gains: [0_i32; 32],
+			values: Self::piece_values(&EvalParams::DEFAULT)
 		}
 	}
 
+	//follows eval params loaded or tuned at runtime
+	pub fn set_eval_params(&mut self, params: &EvalParams) {
+		self.values = Self::piece_values(params);
+	}
+
+	fn piece_values(params: &EvalParams) -> [i32; 6] {
+		[params.pawn.mg(), params.knight.mg(), params.bishop.mg(), params.rook.mg(), params.queen.mg(), Self::KING_VALUE]
+	}
+
 	pub fn see(&mut self, board: &Board, mv: Move) -> i32 {
+		//castling is encoded as the king capturing its own rook
+		if board.colors(board.side_to_move()).has(mv.to) {
+			return 0;
+		}
+
 		self.gains = [0_i32; 32];
+		self.gains[0] = self.move_gain(board, mv);
+
+		let mut on_square = Self::piece_after(board, mv);
+		let mut occupied = Self::occupied_after(board, mv);
+		let mut color = !board.side_to_move();
+		let mut depth = 0;
 
-		let mut max_depth = 0;
-		let mv_piece = board.piece_on(mv.from).unwrap();
+		while depth < self.gains.len() - 1 {
+			let (piece, attacker) = match Self::least_valuable_attacker(board, mv.to, occupied, color) {
+				Some(found) => found,
+				None => break
+			};
 
-		self.gains[0] = if let Some(piece) = board.piece_on(mv.to) {
-			self.piece_pts(piece)
-		} else {
-			if mv_piece == Piece::King {
-				return 0;
-			}
-			0
-		};
+			depth += 1;
+			self.gains[depth] = self.piece_value(on_square) - self.gains[depth - 1];
 
-		let mut color = !board.side_to_move();
-		let mut blockers = board.occupied() & !mv.from.bitboard();
-		let mut last_piece_pts = self.piece_pts(mv_piece);
-	
-		'outer: for i in 1..32 {
-			self.gains[i] = last_piece_pts - self.gains[i - 1];
-
-			let defenders = board.colors(color) & blockers;
-
-			for &piece in &Piece::ALL {
-				last_piece_pts = self.piece_pts(piece);
-
-				let mut victim_square = match piece {
-					Piece::Pawn => {
-						cozy_chess::get_pawn_attacks(mv.to, !color)
-					},
-					Piece::Knight => {
-						cozy_chess::get_knight_moves(mv.to)
-					},
-					Piece::Bishop => {
-						cozy_chess::get_bishop_moves(mv.to, blockers)
-					},
-					Piece::Rook => {
-						cozy_chess::get_rook_moves(mv.to, blockers)
-					},
-					Piece::Queen => {
-						cozy_chess::get_rook_moves(mv.to, blockers)
-							| cozy_chess::get_bishop_moves(mv.to, blockers)
-					},
-					Piece::King => {
-						cozy_chess::get_king_moves(mv.to)
-					}
-				} & board.pieces(piece) & defenders;
-
-				if victim_square != BitBoard::EMPTY {
-					let attacker = victim_square.next_square().unwrap();
-					blockers &= !attacker.bitboard();
-					color = !color;
-					continue 'outer;
-				}
+			on_square = piece;
+			if piece == Piece::Pawn && Self::is_promotion_square(mv.to, color) {
+				self.gains[depth] += self.piece_value(Piece::Queen) - self.piece_value(Piece::Pawn);
+				on_square = Piece::Queen;
 			}
-			max_depth = i;
-			break;
+
+			occupied &= !attacker.bitboard();
+			color = !color;
 		}
-		for depth in (1..max_depth).rev() {
+
+		for depth in (1..=depth).rev() {
 			self.gains[depth - 1] = -i32::max(-self.gains[depth - 1], self.gains[depth]);
 		}
 		self.gains[0]
 	}
 
-	fn piece_pts(&self, piece: Piece) -> i32 {
-		match piece {
-			Piece::Pawn => 100,
-			Piece::Knight => 375,
-			Piece::Bishop => 375,
-			Piece::Rook => 500,
-			Piece::Queen => 1025,
-			Piece::King => 10000
+	//whether mv wins at least threshold, exits as soon as the outcome is known
+	pub fn see_ge(&self, board: &Board, mv: Move, threshold: i32) -> bool {
+		let us = board.side_to_move();
+
+		//castling is encoded as the king capturing its own rook
+		if board.colors(us).has(mv.to) {
+			return 0 >= threshold;
+		}
+
+		let mut balance = self.move_gain(board, mv) - threshold;
+
+		//even winning the target for free is not enough
+		if balance < 0 {
+			return false;
+		}
+
+		balance -= self.piece_value(Self::piece_after(board, mv));
+
+		//even losing the moved piece is still enough
+		if balance >= 0 {
+			return true;
+		}
+
+		let mut occupied = Self::occupied_after(board, mv);
+		let mut color = !us;
+
+		while let Some((mut piece, attacker)) = Self::least_valuable_attacker(board, mv.to, occupied, color) {
+			//a pawn recapturing on the last rank promotes, gaining the promotion and putting a queen at risk
+			let mut promotion_gain = 0;
+			if piece == Piece::Pawn && Self::is_promotion_square(mv.to, color) {
+				piece = Piece::Queen;
+				promotion_gain = self.piece_value(Piece::Queen) - self.piece_value(Piece::Pawn);
+			}
+
+			occupied &= !attacker.bitboard();
+			color = !color;
+			balance = -balance - 1 - self.piece_value(piece) + promotion_gain;
+
+			if balance >= 0 {
+				break;
+			}
+		}
+
+		//the side left to move lost the exchange
+		color != us
+	}
+
+	pub fn piece_value(&self, piece: Piece) -> i32 {
+		self.values[piece as usize]
+	}
+
+	//material won by the move itself, counting en passant and promotion
+	fn move_gain(&self, board: &Board, mv: Move) -> i32 {
+		let mut gain = match board.piece_on(mv.to) {
+			Some(piece) => self.piece_value(piece),
+			None if Self::is_en_passant(board, mv) => self.piece_value(Piece::Pawn),
+			None => 0
+		};
+
+		if let Some(promotion) = mv.promotion {
+			gain += self.piece_value(promotion) - self.piece_value(Piece::Pawn);
+		}
+
+		gain
+	}
+
+	fn piece_after(board: &Board, mv: Move) -> Piece {
+		mv.promotion.unwrap_or(board.piece_on(mv.from).unwrap())
+	}
+
+	fn occupied_after(board: &Board, mv: Move) -> BitBoard {
+		let mut occupied = (board.occupied() & !mv.from.bitboard()) | mv.to.bitboard();
+
+		if Self::is_en_passant(board, mv) {
+			occupied &= !Square::new(mv.to.file(), mv.from.rank()).bitboard();
+		}
+
+		occupied
+	}
+
+	fn is_en_passant(board: &Board, mv: Move) -> bool {
+		board.piece_on(mv.from) == Some(Piece::Pawn) && mv.from.file() != mv.to.file() && board.piece_on(mv.to).is_none()
+	}
+
+	fn is_promotion_square(square: Square, color: Color) -> bool {
+		square.rank() == Rank::Eighth.relative_to(color)
+	}
+
+	//the cheapest piece of color that can capture on square, skipping pieces pinned along another line
+	fn least_valuable_attacker(board: &Board, square: Square, occupied: BitBoard, color: Color) -> Option<(Piece, Square)> {
+		let attackers = Self::attackers(board, square, occupied) & occupied & board.colors(color) & !Self::pinned(board, square, occupied, color);
+
+		for &piece in &Piece::ALL {
+			if let Some(attacker) = (attackers & board.pieces(piece)).next_square() {
+				//the king can not recapture onto a defended square
+				if piece == Piece::King && !(Self::attackers(board, square, occupied & !attacker.bitboard()) & occupied & board.colors(!color)).is_empty() {
+					return None;
+				}
+
+				return Some((piece, attacker));
+			}
+		}
+
+		None
+	}
+
+	//slider attacks are generated with the current occupancy, so pieces behind a capturer join in
+	fn attackers(board: &Board, square: Square, occupied: BitBoard) -> BitBoard {
+		let rooks = board.pieces(Piece::Rook) | board.pieces(Piece::Queen);
+		let bishops = board.pieces(Piece::Bishop) | board.pieces(Piece::Queen);
+
+		(get_pawn_attacks(square, Color::White) & board.colored_pieces(Color::Black, Piece::Pawn))
+			| (get_pawn_attacks(square, Color::Black) & board.colored_pieces(Color::White, Piece::Pawn))
+			| (get_knight_moves(square) & board.pieces(Piece::Knight))
+			| (get_king_moves(square) & board.pieces(Piece::King))
+			| (get_rook_moves(square, occupied) & rooks)
+			| (get_bishop_moves(square, occupied) & bishops)
+	}
+
+	//pieces of color pinned to their king on a line that does not pass through square
+	fn pinned(board: &Board, square: Square, occupied: BitBoard, color: Color) -> BitBoard {
+		let king = board.king(color);
+		let rooks = board.pieces(Piece::Rook) | board.pieces(Piece::Queen);
+		let bishops = board.pieces(Piece::Bishop) | board.pieces(Piece::Queen);
+		let snipers = ((get_rook_rays(king) & rooks) | (get_bishop_rays(king) & bishops)) & board.colors(!color) & occupied;
+
+		let mut pinned = BitBoard::EMPTY;
+		for sniper in snipers {
+			let between = get_between_rays(king, sniper) & occupied;
+
+			if between.len() == 1 && !(between & board.colors(color)).is_empty() && !get_line_rays(king, sniper).has(square) {
+				pinned |= between;
+			}
 		}
+
+		pinned
 	}
-}
\ No newline at end of file
+}
+
+impl See {
+	const KING_VALUE: i32 = 20000;
+}
@@ -0,0 +1,49 @@
+use std::time::Instant;
+
+/*
+Where the time manager reads the time from
+The wall clock is used for real games, the simulated clock turns nodes searched into time
+so games under a time control play out the same way on every run and machine
+*/
+
+pub trait Clock: Send {
+	//milliseconds since the search started, given the nodes the asking thread has searched
+	fn elapsed(&self, nodes: u64) -> u64;
+}
+
+pub struct WallClock {
+	start: Instant
+}
+
+impl WallClock {
+	pub fn new() -> WallClock {
+		WallClock {
+			start: Instant::now()
+		}
+	}
+}
+
+impl Clock for WallClock {
+	fn elapsed(&self, _nodes: u64) -> u64 {
+		self.start.elapsed().as_millis() as u64
+	}
+}
+
+//advances by one millisecond every nps / 1000 nodes
+pub struct SimulatedClock {
+	nps: u64
+}
+
+impl SimulatedClock {
+	pub fn new(nps: u64) -> SimulatedClock {
+		SimulatedClock {
+			nps: nps.max(1)
+		}
+	}
+}
+
+impl Clock for SimulatedClock {
+	fn elapsed(&self, nodes: u64) -> u64 {
+		nodes * 1000 / self.nps
+	}
+}
@@ -1 +1,79 @@
-pub static LMR_TABLE: [[f32; 64]; 64] = [[0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0], [0.0, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.75], [0.0, 0.75, 0.96353465, 1.0884445, 1.1770693, 1.2458122, 1.3019791, 1.3494675, 1.390604, 1.426889, 1.4593468, 1.4887086, 1.5155138, 1.5401721, 1.5630023, 1.5842566, 1.6041386, 1.622815, 1.6404235, 1.6570798, 1.6728816, 1.6879121, 1.7022433, 1.7159374, 1.7290485, 1.7416244, 1.7537068, 1.7653334, 1.776537, 1.7873473, 1.7977912, 1.8078927, 1.8176733, 1.8271531, 1.8363497, 1.8452798, 1.8539582, 1.8623989, 1.8706145, 1.8786167, 1.8864162, 1.8940232, 1.9014468, 1.9086957, 1.9157779, 1.9227011, 1.929472, 1.9360973, 1.9425832, 1.9489352, 1.955159, 1.9612595, 1.9672415, 1.9731096, 1.978868, 1.9845208, 1.9900717, 1.9955243, 2.0008821, 2.0061483, 2.0113258, 2.016418, 2.0214274, 2.0263565], [0.0, 0.75, 1.0884445, 1.2864218, 1.426889, 1.5358436, 1.6248662, 1.7001336, 1.7653334, 1.8228436, 1.8742881, 1.9208255, 1.9633107, 2.0023932, 2.0385783, 2.0722656, 2.103778, 2.133379, 2.161288, 2.1876874, 2.2127328, 2.2365556, 2.25927, 2.2809744, 2.3017552, 2.3216872, 2.3408377, 2.3592653, 2.3770227, 2.3941567, 2.4107099, 2.4267201, 2.442222, 2.4572473, 2.4718237, 2.4859776, 2.4997325, 2.5131106, 2.526132, 2.538815, 2.551177, 2.5632339, 2.5749998, 2.5864892, 2.5977144, 2.6086874, 2.6194189, 2.6299195, 2.6401997, 2.6502671, 2.660132, 2.6698008, 2.6792822, 2.688583, 2.6977098, 2.7066693, 2.715467, 2.7241092, 2.7326012, 2.740948, 2.7491546, 2.757225, 2.7651646, 2.772977], [0.0, 0.75, 1.1770693, 1.426889, 1.6041386, 1.7416244, 1.8539582, 1.9489352, 2.031208, 2.103778, 2.1686935, 2.2274172, 2.2810276, 2.3303442, 2.3760047, 2.4185133, 2.4582772, 2.49563, 2.530847, 2.5641596, 2.5957632, 2.6258242, 2.6544867, 2.6818748, 2.708097, 2.7332487, 2.7574136, 2.7806668, 2.803074, 2.8246946, 2.8455825, 2.8657854, 2.8853467, 2.9043062, 2.9226995, 2.9405596, 2.9579165, 2.9747977, 2.991229, 3.0072334, 3.0228324, 3.0380464, 3.0528936, 3.0673914, 3.0815558, 3.0954022, 3.108944, 3.1221945, 3.1351664, 3.1478703, 3.160318, 3.172519, 3.184483, 3.1962192, 3.207736, 3.2190416, 3.2301433, 3.2410486, 3.251764, 3.2622967, 3.272652, 3.282836, 3.2928548, 3.302713], [0.0, 0.75, 1.2458122, 1.5358436, 1.7416244, 1.9012402, 2.0316558, 2.1419206, 2.2374365, 2.3216872, 2.3970523, 2.4652283, 2.5274682, 2.584723, 2.637733, 2.687084, 2.7332487, 2.776614, 2.8174996, 2.856174, 2.8928647, 2.9277644, 2.9610405, 2.992837, 3.0232801, 3.0524805, 3.080535, 3.107531, 3.1335452, 3.158646, 3.1828961, 3.2063508, 3.229061, 3.251072, 3.272426, 3.293161, 3.3133116, 3.3329103, 3.3519864, 3.3705666, 3.3886766, 3.4063394, 3.4235766, 3.440408, 3.4568527, 3.4729278, 3.4886491, 3.5040326, 3.5190923, 3.5338414, 3.5482926, 3.5624573, 3.5763474, 3.5899725, 3.6033432, 3.6164687, 3.629357, 3.6420178, 3.654458, 3.666686, 3.678708, 3.6905317, 3.7021627, 3.713608], [0.0, 0.75, 1.3019791, 1.6248662, 1.8539582, 2.0316558, 2.1768453, 2.299601, 2.4059374, 2.4997325, 2.5836349, 2.659534, 2.7288244, 2.7925653, 2.8515806, 2.906522, 2.9579165, 3.006194, 3.0517116, 3.0947673, 3.1356142, 3.1744676, 3.2115133, 3.2469118, 3.2808037, 3.3133116, 3.3445444, 3.3745985, 3.4035597, 3.4315042, 3.458501, 3.484613, 3.5098958, 3.5344002, 3.5581732, 3.5812573, 3.6036906, 3.6255095, 3.6467464, 3.6674318, 3.6875932, 3.707257, 3.7264469, 3.745185, 3.7634923, 3.7813883, 3.7988908, 3.816017, 3.8327827, 3.8492024, 3.8652909, 3.8810606, 3.8965235, 3.9116926, 3.9265778, 3.9411898, 3.9555387, 3.9696336, 3.9834833, 3.997096, 4.0104804, 4.023643, 4.036592, 4.0493336], [0.0, 0.75, 1.3494675, 1.7001336, 1.9489352, 2.1419206, 2.299601, 2.432918, 2.5484028, 2.6502671, 2.7413883, 2.8238173, 2.8990688, 2.9682937, 3.0323858, 3.0920544, 3.1478703, 3.2003014, 3.2497349, 3.2964947, 3.3408558, 3.3830519, 3.4232848, 3.4617288, 3.4985363, 3.5338414, 3.5677614, 3.6004012, 3.6318536, 3.6622021, 3.691522, 3.71988, 3.747338, 3.773951, 3.7997692, 3.824839, 3.8492024, 3.8728983, 3.8959625, 3.9184275, 3.9403236, 3.9616787, 3.9825196, 4.0028696, 4.0227523, 4.0421877, 4.0611963, 4.079796, 4.0980043, 4.115836, 4.1333094, 4.1504354, 4.1672287, 4.1837025, 4.1998687, 4.215738, 4.231321, 4.2466288, 4.26167, 4.276454, 4.29099, 4.3052845, 4.319348, 4.333185], [0.0, 0.75, 1.390604, 1.7653334, 2.031208, 2.2374365, 2.4059374, 2.5484028, 2.671812, 2.7806668, 2.8780406, 2.966126, 3.0465415, 3.1205165, 3.189007, 3.25277, 3.312416, 3.3684452, 3.4212708, 3.4712393, 3.5186446, 3.5637362, 3.60673, 3.647812, 3.6871455, 3.724873, 3.7611206, 3.7960002, 3.829611, 3.8620422, 3.893374, 3.9236782, 3.95302, 3.9814594, 4.0090494, 4.0358396, 4.061875, 4.087197, 4.111843, 4.13585, 4.1592484, 4.1820693, 4.2043405, 4.226087, 4.247334, 4.2681036, 4.288416, 4.308292, 4.3277493, 4.3468056, 4.3654766, 4.3837786, 4.401725, 4.4193287, 4.436604, 4.4535627, 4.470215, 4.4865727, 4.5026464, 4.518445, 4.533978, 4.5492544, 4.564282, 4.579069], [0.0, 0.75, 1.426889, 1.8228436, 2.103778, 2.3216872, 2.4997325, 2.6502671, 2.7806668, 2.895687, 2.9985762, 3.091651, 3.1766214, 3.2547865, 3.3271565, 3.394531, 3.4575555, 3.5167582, 3.572576, 3.6253748, 3.6754653, 3.7231112, 3.76854, 3.811949, 3.8535104, 3.8933747, 3.9316754, 3.9685307, 4.0040455, 4.0383134, 4.0714197, 4.1034403, 4.134444, 4.1644945, 4.1936474, 4.2219553, 4.249465, 4.2762213, 4.302264, 4.32763, 4.352354, 4.3764677, 4.3999996, 4.4229784, 4.445429, 4.467375, 4.4888377, 4.509839, 4.5303993, 4.5505342, 4.570264, 4.5896015, 4.6085644, 4.627166, 4.6454196, 4.6633387, 4.680934, 4.6982183, 4.7152023, 4.731896, 4.748309, 4.76445, 4.780329, 4.795954], [0.0, 0.75, 1.4593468, 1.8742881, 2.1686935, 2.3970523, 2.5836349, 2.7413883, 2.8780406, 2.9985762, 3.1063993, 3.2039368, 3.2929819, 3.374895, 3.4507353, 3.5213404, 3.5873873, 3.6494288, 3.7079232, 3.763254, 3.815746, 3.8656766, 3.9132838, 3.9587743, 4.002329, 4.0441046, 4.084242, 4.1228647, 4.160082, 4.1959934, 4.230687, 4.264243, 4.2967343, 4.328225, 4.3587756, 4.388441, 4.4172697, 4.445309, 4.472601, 4.499183, 4.525093, 4.5503626, 4.575023, 4.599104, 4.6226306, 4.645629, 4.6681213, 4.69013, 4.7116756, 4.7327766, 4.7534513, 4.773717, 4.7935886, 4.813082, 4.8322115, 4.8509893, 4.8694286, 4.887542, 4.90534, 4.9228344, 4.9400344, 4.956949, 4.9735904, 4.9899645], [0.0, 0.75, 1.4887086, 1.9208255, 2.2274172, 2.4652283, 2.659534, 2.8238173, 2.966126, 3.091651, 3.2039368, 3.3055122, 3.3982427, 3.4835465, 3.562526, 3.6360538, 3.7048345, 3.769444, 3.8303595, 3.8879805, 3.9426455, 3.994643, 4.044221, 4.091594, 4.1369514, 4.1804566, 4.222255, 4.2624764, 4.3012342, 4.3386326, 4.3747625, 4.409707, 4.443543, 4.4763374, 4.5081525, 4.5390453, 4.569068, 4.5982676, 4.626689, 4.654372, 4.681354, 4.70767, 4.7333517, 4.7584286, 4.7829294, 4.806879, 4.8303027, 4.853223, 4.87566, 4.8976345, 4.919165, 4.9402695, 4.9609637, 4.981264, 5.001185, 5.0207405, 5.0399427, 5.058806, 5.077341, 5.095559, 5.113471, 5.1310863, 5.148416, 5.165468], [0.0, 0.75, 1.5155138, 1.9633107, 2.2810276, 2.5274682, 2.7288244, 2.8990688, 3.0465415, 3.1766214, 3.2929819, 3.3982427, 3.4943383, 3.5827377, 3.6645827, 3.740779, 3.812055, 3.8790092, 3.9421353, 4.0018473, 4.0584955, 4.11238, 4.1637564, 4.212849, 4.2598524, 4.3049364, 4.3482513, 4.3899317, 4.4300966, 4.468851, 4.5062923, 4.5425053, 4.577569, 4.6115537, 4.644523, 4.676537, 4.7076488, 4.7379084, 4.7673607, 4.7960486, 4.8240094, 4.8512797, 4.8778934, 4.9038806, 4.9292703, 4.954089, 4.978363, 5.0021143, 5.025366, 5.0481377, 5.07045, 5.09232, 5.1137652, 5.134802, 5.155446, 5.1757107, 5.19561, 5.215158, 5.2343655, 5.2532444, 5.2718067, 5.290061, 5.3080196, 5.32569], [0.0, 0.75, 1.5401721, 2.0023932, 2.3303442, 2.584723, 2.7925653, 2.9682937, 3.1205165, 3.2547865, 3.374895, 3.4835465, 3.5827377, 3.6739843, 3.7584658, 3.8371162, 3.9106886, 3.9797993, 4.044959, 4.106594, 4.165067, 4.220687, 4.273719, 4.3243923, 4.3729095, 4.419446, 4.4641566, 4.5071797, 4.5486383, 4.588641, 4.6272883, 4.664668, 4.700861, 4.73594, 4.769972, 4.8030167, 4.8351307, 4.866365, 4.896766, 4.926378, 4.95524, 4.9833884, 5.010859, 5.0376835, 5.063891, 5.0895095, 5.114565, 5.1390815, 5.1630816, 5.1865873, 5.209618, 5.2321925, 5.2543287, 5.2760434, 5.297352, 5.3182697, 5.33881, 5.3589873, 5.3788133, 5.398301, 5.4174604, 5.4363036, 5.4548397, 5.47308], [0.0, 0.75, 1.5630023, 2.0385783, 2.3760047, 2.637733, 2.8515806, 3.0323858, 3.189007, 3.3271565, 3.4507353, 3.562526, 3.6645827, 3.7584658, 3.8453884, 3.926311, 4.0020094, 4.073117, 4.1401587, 4.203575, 4.2637377, 4.3209643, 4.3755283, 4.427666, 4.4775853, 4.525466, 4.5714684, 4.6157346, 4.658391, 4.6995497, 4.739313, 4.777773, 4.815012, 4.8511043, 4.886119, 4.920119, 4.9531612, 4.985297, 5.016577, 5.0470443, 5.07674, 5.1057024, 5.1339664, 5.161566, 5.1885304, 5.2148895, 5.240669, 5.2658935, 5.2905874, 5.3147717, 5.3384686, 5.361695, 5.3844705, 5.4068127, 5.4287367, 5.4502587, 5.4713926, 5.492153, 5.5125523, 5.5326023, 5.5523157, 5.571703, 5.5907755, 5.6095424], [0.0, 0.75, 1.5842566, 2.0722656, 2.4185133, 2.687084, 2.906522, 3.0920544, 3.25277, 3.394531, 3.5213404, 3.6360538, 3.740779, 3.8371162, 3.926311, 4.0093493, 4.0870266, 4.159993, 4.2287874, 4.2938614, 4.3555975, 4.41432, 4.4703107, 4.5238113, 4.5750356, 4.624168, 4.6713724, 4.7167964, 4.7605677, 4.8028026, 4.843606, 4.883071, 4.9212832, 4.958319, 4.9942493, 5.0291386, 5.063044, 5.096021, 5.1281185, 5.159382, 5.1898537, 5.219573, 5.2485766, 5.2768974, 5.3045673, 5.331615, 5.3580685, 5.383952, 5.409292, 5.4341087, 5.4584246, 5.4822583, 5.5056295, 5.5285554, 5.551053, 5.5731378, 5.594824, 5.616127, 5.6370597, 5.657634, 5.6778626, 5.697757, 5.7173276, 5.736585], [0.0, 0.75, 1.6041386, 2.103778, 2.4582772, 2.7332487, 2.9579165, 3.1478703, 3.312416, 3.4575555, 3.5873873, 3.7048345, 3.812055, 3.9106886, 4.0020094, 4.0870266, 4.1665545, 4.24126, 4.311694, 4.3783193, 4.4415264, 4.5016484, 4.5589733, 4.6137495, 4.666194, 4.7164974, 4.7648273, 4.8113337, 4.856148, 4.8993893, 4.941165, 4.9815707, 5.0206933, 5.0586123, 5.095399, 5.1311193, 5.165833, 5.1995955, 5.232458, 5.264467, 5.295665, 5.3260927, 5.3557873, 5.384783, 5.4131117, 5.4408045, 5.467888, 5.494389, 5.520333, 5.5457406, 5.570636, 5.595038, 5.618966, 5.6424384, 5.665472, 5.688083, 5.7102866, 5.732097, 5.753528, 5.7745934, 5.795304, 5.815672, 5.8357096, 5.855426], [0.0, 0.75, 1.622815, 2.133379, 2.49563, 2.776614, 3.006194, 3.2003014, 3.3684452, 3.5167582, 3.6494288, 3.769444, 3.8790092, 3.9797993, 4.073117, 4.159993, 4.24126, 4.3175993, 4.389573, 4.457655, 4.522244, 4.583681, 4.6422586, 4.6982327, 4.7518244, 4.803228, 4.8526144, 4.9001374, 4.945932, 4.9901185, 5.0328083, 5.074097, 5.114075, 5.152823, 5.190414, 5.2269154, 5.262388, 5.296889, 5.33047, 5.3631783, 5.395059, 5.4261518, 5.456496, 5.4861255, 5.515074, 5.5433717, 5.571048, 5.5981283, 5.6246395, 5.650603, 5.6760426, 5.7009783, 5.7254295, 5.749415, 5.7729526, 5.7960577, 5.8187466, 5.841034, 5.8629336, 5.8844595, 5.905623, 5.9264364, 5.9469123, 5.9670596], [0.0, 0.75, 1.6404235, 2.161288, 2.530847, 2.8174996, 3.0517116, 3.2497349, 3.4212708, 3.572576, 3.7079232, 3.8303595, 3.9421353, 4.044959, 4.1401587, 4.2287874, 4.311694, 4.389573, 4.4629993, 4.5324545, 4.5983467, 4.661023, 4.720783, 4.7778864, 4.832559, 4.8849993, 4.9353824, 4.9838643, 5.0305824, 5.0756607, 5.119211, 5.161333, 5.202118, 5.2416477, 5.279997, 5.317235, 5.353423, 5.3886204, 5.4228783, 5.456247, 5.4887705, 5.5204906, 5.551447, 5.5816746, 5.611207, 5.6400757, 5.66831, 5.6959367, 5.7229824, 5.7494698, 5.775423, 5.8008614, 5.8258057, 5.850275, 5.8742876, 5.8978596, 5.9210057, 5.9437428, 5.9660845, 5.9880443, 6.0096345, 6.030868, 6.0517564, 6.072311], [0.0, 0.75, 1.6570798, 2.1876874, 2.5641596, 2.856174, 3.0947673, 3.2964947, 3.4712393, 3.6253748, 3.763254, 3.8879805, 4.0018473, 4.106594, 4.203575, 4.2938614, 4.3783193, 4.457655, 4.5324545, 4.6032095, 4.670334, 4.7341824, 4.7950606, 4.8532314, 4.908927, 4.962348, 5.013674, 5.0630627, 5.110655, 5.156576, 5.200941, 5.243851, 5.285399, 5.325668, 5.3647346, 5.4026694, 5.4395347, 5.4753895, 5.510289, 5.5442815, 5.5774136, 5.6097274, 5.6412625, 5.6720552, 5.7021403, 5.7315493, 5.7603116, 5.788455, 5.8160067, 5.8429894, 5.869428, 5.8953424, 5.9207535, 5.9456806, 5.9701424, 5.9941545, 6.017734, 6.0408964, 6.0636563, 6.086027, 6.1080213, 6.129652, 6.150931, 6.1718693], [0.0, 0.75, 1.6728816, 2.2127328, 2.5957632, 2.8928647, 3.1356142, 3.3408558, 3.5186446, 3.6754653, 3.815746, 3.9426455, 4.0584955, 4.165067, 4.2637377, 4.3555975, 4.4415264, 4.522244, 4.5983467, 4.670334, 4.7386274, 4.803589, 4.865527, 4.9247117, 4.981377, 5.0357294, 5.087949, 5.138198, 5.1866193, 5.2333407, 5.2784786, 5.322136, 5.3644075, 5.4053783, 5.4451256, 5.483721, 5.5212283, 5.557708, 5.593215, 5.6278, 5.661509, 5.6943855, 5.72647, 5.757799, 5.7884083, 5.81833, 5.8475933, 5.876227, 5.9042587, 5.9317117, 5.9586105, 5.9849763, 6.0108304, 6.036192, 6.061079, 6.0855103, 6.1095004, 6.133066, 6.1562223, 6.1789827, 6.20136, 6.2233677, 6.2450175, 6.266321], [0.0, 0.75, 1.6879121, 2.2365556, 2.6258242, 2.9277644, 3.1744676, 3.3830519, 3.5637362, 3.7231112, 3.8656766, 3.994643, 4.11238, 4.220687, 4.3209643, 4.41432, 4.5016484, 4.583681, 4.661023, 4.7341824, 4.803589, 4.869608, 4.9325547, 4.9927034, 5.0502915, 5.105529, 5.158599, 5.2096663, 5.2588763, 5.3063593, 5.352232, 5.3966007, 5.4395604, 5.4811983, 5.521593, 5.560817, 5.598935, 5.636009, 5.6720943, 5.707243, 5.741501, 5.774913, 5.80752, 5.8393593, 5.8704667, 5.9008756, 5.930616, 5.959716, 5.9882035, 6.0161037, 6.043441, 6.070236, 6.0965114, 6.122286, 6.1475787, 6.172407, 6.1967883, 6.220738, 6.2442713, 6.267402, 6.290144, 6.31251, 6.3345127, 6.356163], [0.0, 0.75, 1.7022433, 2.25927, 2.6544867, 2.9610405, 3.2115133, 3.4232848, 3.60673, 3.76854, 3.9132838, 4.044221, 4.1637564, 4.273719, 4.3755283, 4.4703107, 4.5589733, 4.6422586, 4.720783, 4.7950606, 4.865527, 4.9325547, 4.996464, 5.0575314, 5.1159997, 5.172081, 5.2259617, 5.27781, 5.3277717, 5.3759794, 5.4225535, 5.4676, 5.5112166, 5.5534906, 5.5945024, 5.6343255, 5.6730266, 5.710667, 5.747304, 5.7829885, 5.81777, 5.851693, 5.884798, 5.9171243, 5.948707, 5.9795804, 6.009775, 6.03932, 6.068243, 6.0965695, 6.1243243, 6.1515293, 6.1782055, 6.204374, 6.230053, 6.2552614, 6.2800145, 6.3043303, 6.3282228, 6.3517075, 6.374797, 6.397505, 6.4198437, 6.4418244], [0.0, 0.75, 1.7159374, 2.2809744, 2.6818748, 2.992837, 3.2469118, 3.4617288, 3.647812, 3.811949, 3.9587743, 4.091594, 4.212849, 4.3243923, 4.427666, 4.5238113, 4.6137495, 4.6982327, 4.7778864, 4.8532314, 4.9247117, 4.9927034, 5.0575314, 5.1194773, 5.1787863, 5.235674, 5.2903304, 5.3429236, 5.393604, 5.442505, 5.489749, 5.5354433, 5.5796866, 5.6225686, 5.6641703, 5.704566, 5.7438235, 5.7820053, 5.819169, 5.855367, 5.8906493, 5.9250593, 5.9586406, 5.9914317, 6.023469, 6.0547857, 6.085415, 6.1153846, 6.144724, 6.1734576, 6.2016115, 6.2292075, 6.2562675, 6.282812, 6.308861, 6.334431, 6.359541, 6.3842063, 6.408442, 6.4322643, 6.4556866, 6.47872, 6.5013804, 6.5236773], [0.0, 0.75, 1.7290485, 2.3017552, 2.708097, 3.0232801, 3.2808037, 3.4985363, 3.6871455, 3.8535104, 4.002329, 4.1369514, 4.2598524, 4.3729095, 4.4775853, 4.5750356, 4.666194, 4.7518244, 4.832559, 4.908927, 4.981377, 5.0502915, 5.1159997, 5.1787863, 5.2389007, 5.2965603, 5.3519583, 5.4052653, 5.4566336, 5.506199, 5.554084, 5.600398, 5.6452427, 5.6887064, 5.7308726, 5.7718167, 5.8116074, 5.850307, 5.8879757, 5.924665, 5.9604254, 5.995303, 6.0293403, 6.0625763, 6.0950484, 6.1267905, 6.157835, 6.1882114, 6.217949, 6.2470727, 6.275609, 6.3035793, 6.331007, 6.357912, 6.3843136, 6.4102316, 6.435682, 6.460682, 6.485247, 6.5093927, 6.5331326, 6.556479, 6.579447, 6.6020465], [0.0, 0.75, 1.7416244, 2.3216872, 2.7332487, 3.0524805, 3.3133116, 3.5338414, 3.724873, 3.8933747, 4.0441046, 4.1804566, 4.3049364, 4.419446, 4.525466, 4.624168, 4.7164974, 4.803228, 4.8849993, 4.962348, 5.0357294, 5.105529, 5.172081, 5.235674, 5.2965603, 5.354961, 5.41107, 5.465062, 5.5170903, 5.567292, 5.6157923, 5.6627016, 5.708122, 5.752144, 5.794852, 5.836322, 5.876623, 5.9158206, 5.953973, 5.991133, 6.0273533, 6.062679, 6.097153, 6.130816, 6.1637053, 6.1958556, 6.2272983, 6.258065, 6.2881846, 6.3176827, 6.3465853, 6.3749146, 6.4026947, 6.429945, 6.4566865, 6.4829373, 6.508714, 6.5340357, 6.558916, 6.583372, 6.607416, 6.6310635, 6.6543255, 6.677216], [0.0, 0.75, 1.7537068, 2.3408377, 2.7574136, 3.080535, 3.3445444, 3.5677614, 3.7611206, 3.9316754, 4.084242, 4.222255, 4.3482513, 4.4641566, 4.5714684, 4.6713724, 4.7648273, 4.8526144, 4.9353824, 5.013674, 5.087949, 5.158599, 5.2259617, 5.2903304, 5.3519583, 5.41107, 5.467863, 5.522513, 5.575175, 5.6259885, 5.67508, 5.7225604, 5.768534, 5.813093, 5.8563213, 5.8982964, 5.939089, 5.9787636, 6.0173807, 6.0549946, 6.0916557, 6.1274114, 6.162306, 6.1963787, 6.229669, 6.2622104, 6.294037, 6.3251786, 6.355665, 6.385523, 6.4147773, 6.4434524, 6.4715705, 6.4991527, 6.5262203, 6.55279, 6.5788817, 6.6045117, 6.6296954, 6.654449, 6.6787863, 6.7027216, 6.7262673, 6.749437], [0.0, 0.75, 1.7653334, 2.3592653, 2.7806668, 3.107531, 3.3745985, 3.6004012, 3.7960002, 3.9685307, 4.1228647, 4.2624764, 4.3899317, 4.5071797, 4.6157346, 4.7167964, 4.8113337, 4.9001374, 4.9838643, 5.0630627, 5.138198, 5.2096663, 5.27781, 5.3429236, 5.4052653, 5.465062, 5.522513, 5.577796, 5.6310678, 5.6824703, 5.73213, 5.780161, 5.826667, 5.871742, 5.9154706, 5.9579325, 5.999197, 6.0393314, 6.078396, 6.1164455, 6.1535316, 6.189701, 6.225, 6.2594676, 6.2931433, 6.3260617, 6.358257, 6.389759, 6.420599, 6.4508023, 6.4803953, 6.5094028, 6.5378466, 6.5657487, 6.5931296, 6.6200075, 6.646401, 6.6723275, 6.6978035, 6.722844, 6.747463, 6.771675, 6.795494, 6.8189316], [0.0, 0.75, 1.776537, 2.3770227, 2.803074, 3.1335452, 3.4035597, 3.6318536, 3.829611, 4.0040455, 4.160082, 4.3012342, 4.4300966, 4.5486383, 4.658391, 4.7605677, 4.856148, 4.945932, 5.0305824, 5.110655, 5.1866193, 5.2588763, 5.3277717, 5.393604, 5.4566336, 5.5170903, 5.575175, 5.6310678, 5.6849275, 5.736897, 5.7871046, 5.8356657, 5.8826847, 5.9282575, 5.972469, 6.0153985, 6.0571194, 6.0976963, 6.137192, 6.175661, 6.2131557, 6.2497253, 6.2854137, 6.3202615, 6.3543086, 6.3875904, 6.4201407, 6.4519906, 6.4831705, 6.513707, 6.543627, 6.572954, 6.601712, 6.6299224, 6.657605, 6.6847796, 6.7114644, 6.737677, 6.763434, 6.788751, 6.813642, 6.838121, 6.8622026, 6.8858986], [0.0, 0.75, 1.7873473, 2.3941567, 2.8246946, 3.158646, 3.4315042, 3.6622021, 3.8620422, 4.0383134, 4.1959934, 4.3386326, 4.468851, 4.588641, 4.6995497, 4.8028026, 4.8993893, 4.9901185, 5.0756607, 5.156576, 5.2333407, 5.3063593, 5.3759794, 5.442505, 5.506199, 5.567292, 5.6259885, 5.6824703, 5.736897, 5.7894135, 5.8401504, 5.8892226, 5.9367366, 5.982789, 6.0274663, 6.0708485, 6.1130085, 6.1540127, 6.1939235, 6.2327976, 6.270688, 6.307642, 6.343706, 6.3789215, 6.413327, 6.4469595, 6.4798527, 6.5120378, 6.543546, 6.5744042, 6.604639, 6.6342754, 6.663336, 6.691843, 6.719817, 6.747278, 6.7742443, 6.800733, 6.8267612, 6.8523445, 6.877497, 6.9022346, 6.9265695, 6.9505153], [0.0, 0.75, 1.7977912, 2.4107099, 2.8455825, 3.1828961, 3.458501, 3.691522, 3.893374, 4.0714197, 4.230687, 4.3747625, 4.5062923, 4.6272883, 4.739313, 4.843606, 4.941165, 5.0328083, 5.119211, 5.200941, 5.2784786, 5.352232, 5.4225535, 5.489749, 5.554084, 5.6157923, 5.67508, 5.73213, 5.7871046, 5.8401504, 5.8913975, 5.9409637, 5.9889565, 6.0354724, 6.080599, 6.1244183, 6.167002, 6.20842, 6.2487326, 6.287998, 6.3262696, 6.363596, 6.4000235, 6.435593, 6.470345, 6.5043163, 6.53754, 6.57005, 6.6018753, 6.633044, 6.6635838, 6.6935177, 6.7228713, 6.751665, 6.779921, 6.8076587, 6.8348956, 6.8616514, 6.8879414, 6.913782, 6.9391885, 6.9641747, 6.988755, 7.0129414], [0.0, 0.75, 1.8078927, 2.4267201, 2.8657854, 3.2063508, 3.484613, 3.71988, 3.9236782, 4.1034403, 4.264243, 4.409707, 4.5425053, 4.664668, 4.777773, 4.883071, 4.9815707, 5.074097, 5.161333, 5.243851, 5.322136, 5.3966007, 5.4676, 5.5354433, 5.600398, 5.6627016, 5.7225604, 5.780161, 5.8356657, 5.8892226, 5.9409637, 5.991008, 6.0394635, 6.0864277, 6.13199, 6.176231, 6.219226, 6.261042, 6.301744, 6.341388, 6.3800287, 6.417715, 6.454493, 6.4904056, 6.5254927, 6.559791, 6.593336, 6.6261587, 6.658291, 6.68976, 6.7205944, 6.7508173, 6.780453, 6.809525, 6.838053, 6.8660583, 6.893558, 6.920572, 6.947115, 6.973205, 6.9988565, 7.0240836, 7.0489006, 7.0733204], [0.0, 0.75, 1.8176733, 2.442222, 2.8853467, 3.229061, 3.5098958, 3.747338, 3.95302, 4.134444, 4.2967343, 4.443543, 4.577569, 4.700861, 4.815012, 4.9212832, 5.0206933, 5.114075, 5.202118, 5.285399, 5.3644075, 5.4395604, 5.5112166, 5.5796866, 5.6452427, 5.708122, 5.768534, 5.826667, 5.8826847, 5.9367366, 5.9889565, 6.0394635, 6.088367, 6.1357656, 6.181749, 6.226399, 6.2697916, 6.3119946, 6.353072, 6.393083, 6.432081, 6.4701157, 6.507234, 6.5434785, 6.57889, 6.6135054, 6.64736, 6.6804867, 6.7129154, 6.744676, 6.775795, 6.8062973, 6.8362074, 6.865548, 6.89434, 6.922604, 6.950358, 6.977621, 7.0044103, 7.030741, 7.05663, 7.0820904, 7.1071367, 7.1317825], [0.0, 0.75, 1.8271531, 2.4572473, 2.9043062, 3.251072, 3.5344002, 3.773951, 3.9814594, 4.1644945, 4.328225, 4.4763374, 4.6115537, 4.73594, 4.8511043, 4.958319, 5.0586123, 5.152823, 5.2416477, 5.325668, 5.4053783, 5.4811983, 5.5534906, 5.6225686, 5.6887064, 5.752144, 5.813093, 5.871742, 5.9282575, 5.982789, 6.0354724, 6.0864277, 6.1357656, 6.1835847, 6.229976, 6.275023, 6.3188004, 6.3613787, 6.402821, 6.443187, 6.482531, 6.5209036, 6.5583515, 6.594918, 6.630644, 6.665567, 6.699722, 6.7331424, 6.7658596, 6.797902, 6.829297, 6.8600707, 6.890246, 6.919847, 6.948895, 6.9774094, 7.00541, 7.032915, 7.0599422, 7.0865073, 7.1126256, 7.1383114, 7.1635804, 7.188445], [0.0, 0.75, 1.8363497, 2.4718237, 2.9226995, 3.272426, 3.5581732, 3.7997692, 4.0090494, 4.1936474, 4.3587756, 4.5081525, 4.644523, 4.769972, 4.886119, 4.9942493, 5.095399, 5.190414, 5.279997, 5.3647346, 5.4451256, 5.521593, 5.5945024, 5.6641703, 5.7308726, 5.794852, 5.8563213, 5.9154706, 5.972469, 6.0274663, 6.080599, 6.13199, 6.181749, 6.229976, 6.276764, 6.322195, 6.3663464, 6.4092884, 6.4510846, 6.491795, 6.531475, 6.570175, 6.6079426, 6.644821, 6.6808524, 6.716073, 6.75052, 6.784226, 6.8172226, 6.8495383, 6.8812013, 6.9122376, 6.942671, 6.9725246, 7.0018206, 7.0305786, 7.058818, 7.0865583, 7.113816, 7.140608, 7.166949, 7.1928544, 7.2183394, 7.2434163], [0.0, 0.75, 1.8452798, 2.4859776, 2.9405596, 3.293161, 3.5812573, 3.824839, 4.0358396, 4.2219553, 4.388441, 4.5390453, 4.676537, 4.8030167, 4.920119, 5.0291386, 5.1311193, 5.2269154, 5.317235, 5.4026694, 5.483721, 5.560817, 5.6343255, 5.704566, 5.7718167, 5.836322, 5.8982964, 5.9579325, 6.0153985, 6.0708485, 6.1244183, 6.176231, 6.226399, 6.275023, 6.322195, 6.368, 6.4125147, 6.4558086, 6.497949, 6.5389943, 6.5790005, 6.618018, 6.6560965, 6.6932783, 6.729605, 6.7651157, 6.7998457, 6.833829, 6.867097, 6.899678, 6.9316015, 6.9628925, 6.9935765, 7.0236754, 7.053212, 7.0822067, 7.110678, 7.1386466, 7.166128, 7.19314, 7.219698, 7.2458167, 7.2715106, 7.2967935], [0.0, 0.75, 1.8539582, 2.4997325, 2.9579165, 3.3133116, 3.6036906, 3.8492024, 4.061875, 4.249465, 4.4172697, 4.569068, 4.7076488, 4.8351307, 4.9531612, 5.063044, 5.165833, 5.262388, 5.353423, 5.4395347, 5.5212283, 5.598935, 5.6730266, 5.7438235, 5.8116074, 5.876623, 5.939089, 5.999197, 6.0571194, 6.1130085, 6.167002, 6.219226, 6.2697916, 6.3188004, 6.3663464, 6.4125147, 6.4573812, 6.501019, 6.543493, 6.5848637, 6.6251864, 6.664514, 6.7028937, 6.74037, 6.7769847, 6.8127766, 6.8477817, 6.882034, 6.9155655, 6.948405, 6.9805818, 7.012121, 7.043047, 7.0733852, 7.1031556, 7.1323795, 7.1610775, 7.189267, 7.2169666, 7.244192, 7.270961, 7.297286, 7.323184, 7.348667], [0.0, 0.75, 1.8623989, 2.5131106, 2.9747977, 3.3329103, 3.6255095, 3.8728983, 4.087197, 4.2762213, 4.445309, 4.5982676, 4.7379084, 4.866365, 4.985297, 5.096021, 5.1995955, 5.296889, 5.3886204, 5.4753895, 5.557708, 5.636009, 5.710667, 5.7820053, 5.850307, 5.9158206, 5.9787636, 6.0393314, 6.0976963, 6.1540127, 6.20842, 6.261042, 6.3119946, 6.3613787, 6.4092884, 6.4558086, 6.501019, 6.54499, 6.5877886, 6.6294756, 6.670107, 6.709735, 6.748408, 6.786171, 6.8230658, 6.8591313, 6.894404, 6.9289184, 6.962706, 6.9957967, 7.028219, 7.0599995, 7.0911627, 7.121732, 7.1517305, 7.181178, 7.210095, 7.2385006, 7.2664113, 7.2938457, 7.3208184, 7.3473454, 7.373441, 7.3991194], [0.0, 0.75, 1.8706145, 2.526132, 2.991229, 3.3519864, 3.6467464, 3.8959625, 4.111843, 4.302264, 4.472601, 4.626689, 4.7673607, 4.896766, 5.016577, 5.1281185, 5.232458, 5.33047, 5.4228783, 5.510289, 5.593215, 5.6720943, 5.747304, 5.819169, 5.8879757, 5.953973, 6.0173807, 6.078396, 6.137192, 6.1939235, 6.2487326, 6.301744, 6.353072, 6.402821, 6.4510846, 6.497949, 6.543493, 6.5877886, 6.6309032, 6.6728983, 6.71383, 6.7537503, 6.792709, 6.830751, 6.867918, 6.90425, 6.939783, 6.9745526, 7.0085897, 7.041925, 7.074587, 7.106602, 7.137995, 7.168791, 7.1990104, 7.2286754, 7.257806, 7.286421, 7.3145385, 7.342175, 7.369347, 7.39607, 7.4223585, 7.448226], [0.0, 0.75, 1.8786167, 2.538815, 3.0072334, 3.3705666, 3.6674318, 3.9184275, 4.13585, 4.32763, 4.499183, 4.654372, 4.7960486, 4.926378, 5.0470443, 5.159382, 5.264467, 5.3631783, 5.456247, 5.5442815, 5.6278, 5.707243, 5.7829885, 5.855367, 5.924665, 5.991133, 6.0549946, 6.1164455, 6.175661, 6.2327976, 6.287998, 6.341388, 6.393083, 6.443187, 6.491795, 6.5389943, 6.5848637, 6.6294756, 6.6728983, 6.715193, 6.7564163, 6.796622, 6.8358593, 6.8741727, 6.9116054, 6.948197, 6.983984, 7.019001, 7.053282, 7.086855, 7.11975, 7.1519938, 7.183611, 7.214626, 7.245062, 7.2749386, 7.304277, 7.3330965, 7.3614144, 7.389249, 7.416615, 7.4435287, 7.4700046, 7.4960575], [0.0, 0.75, 1.8864162, 2.551177, 3.0228324, 3.3886766, 3.6875932, 3.9403236, 4.1592484, 4.352354, 4.525093, 4.681354, 4.8240094, 4.95524, 5.07674, 5.1898537, 5.295665, 5.395059, 5.4887705, 5.5774136, 5.661509, 5.741501, 5.81777, 5.8906493, 5.9604254, 6.0273533, 6.0916557, 6.1535316, 6.2131557, 6.270688, 6.3262696, 6.3800287, 6.432081, 6.482531, 6.531475, 6.5790005, 6.6251864, 6.670107, 6.71383, 6.7564163, 6.7979255, 6.8384085, 6.877917, 6.916495, 6.954187, 6.9910307, 7.0270653, 7.0623245, 7.096842, 7.130647, 7.1637697, 7.196236, 7.2280717, 7.2593017, 7.2899475, 7.320031, 7.3495717, 7.3785906, 7.407104, 7.435131, 7.4626865, 7.4897857, 7.516445, 7.5426774], [0.0, 0.75, 1.8940232, 2.5632339, 3.0380464, 3.4063394, 3.707257, 3.9616787, 4.1820693, 4.3764677, 4.5503626, 4.70767, 4.8512797, 4.9833884, 5.1057024, 5.219573, 5.3260927, 5.4261518, 5.5204906, 5.6097274, 5.6943855, 5.774913, 5.851693, 5.9250593, 5.995303, 6.062679, 6.1274114, 6.189701, 6.2497253, 6.307642, 6.363596, 6.417715, 6.4701157, 6.5209036, 6.570175, 6.618018, 6.664514, 6.709735, 6.7537503, 6.796622, 6.8384085, 6.8791633, 6.918936, 6.9577723, 6.995716, 7.032807, 7.0690823, 7.104578, 7.139326, 7.1733575, 7.2067018, 7.2393856, 7.271435, 7.3028736, 7.3337245, 7.3640094, 7.393748, 7.4229608, 7.4516654, 7.4798794, 7.5076194, 7.5349, 7.561738, 7.588146], [0.0, 0.75, 1.9014468, 2.5749998, 3.0528936, 3.4235766, 3.7264469, 3.9825196, 4.2043405, 4.3999996, 4.575023, 4.7333517, 4.8778934, 5.010859, 5.1339664, 5.2485766, 5.3557873, 5.456496, 5.551447, 5.6412625, 5.72647, 5.80752, 5.884798, 5.9586406, 6.0293403, 6.097153, 6.162306, 6.225, 6.2854137, 6.343706, 6.4000235, 6.454493, 6.507234, 6.5583515, 6.6079426, 6.6560965, 6.7028937, 6.748408, 6.792709, 6.8358593, 6.877917, 6.918936, 6.9589667, 6.998055, 7.0362453, 7.0735765, 7.1100874, 7.145813, 7.180787, 7.2150393, 7.2486, 7.281496, 7.313753, 7.3453956, 7.3764467, 7.406928, 7.4368596, 7.4662623, 7.4951534, 7.52355, 7.55147, 7.578928, 7.60594, 7.6325197], [0.0, 0.75, 1.9086957, 2.5864892, 3.0673914, 3.440408, 3.745185, 4.0028696, 4.226087, 4.4229784, 4.599104, 4.7584286, 4.9038806, 5.0376835, 5.161566, 5.2768974, 5.384783, 5.4861255, 5.5816746, 5.6720552, 5.757799, 5.8393593, 5.9171243, 5.9914317, 6.0625763, 6.130816, 6.1963787, 6.2594676, 6.3202615, 6.3789215, 6.435593, 6.4904056, 6.5434785, 6.594918, 6.644821, 6.6932783, 6.74037, 6.786171, 6.830751, 6.8741727, 6.916495, 6.9577723, 6.998055, 7.0373898, 7.07582, 7.1133866, 7.1501274, 7.186078, 7.221272, 7.2557397, 7.2895117, 7.3226147, 7.355075, 7.3869166, 7.4181633, 7.448837, 7.4789567, 7.5085444, 7.537617, 7.5661936, 7.594289, 7.6219196, 7.6491017, 7.675848], [0.0, 0.75, 1.9157779, 2.5977144, 3.0815558, 3.4568527, 3.7634923, 4.0227523, 4.247334, 4.445429, 4.6226306, 4.7829294, 4.9292703, 5.063891, 5.1885304, 5.3045673, 5.4131117, 5.515074, 5.611207, 5.7021403, 5.7884083, 5.8704667, 5.948707, 6.023469, 6.0950484, 6.1637053, 6.229669, 6.2931433, 6.3543086, 6.413327, 6.470345, 6.5254927, 6.57889, 6.630644, 6.6808524, 6.729605, 6.7769847, 6.8230658, 6.867918, 6.9116054, 6.954187, 6.995716, 7.0362453, 7.07582, 7.114485, 7.1522813, 7.1892467, 7.225417, 7.260826, 7.2955046, 7.3294835, 7.362788, 7.395447, 7.4274836, 7.4589214, 7.4897823, 7.5200863, 7.5498548, 7.579105, 7.607856, 7.6361227, 7.663923, 7.691271, 7.718181], [0.0, 0.75, 1.9227011, 2.6086874, 3.0954022, 3.4729278, 3.7813883, 4.0421877, 4.2681036, 4.467375, 4.645629, 4.806879, 4.954089, 5.0895095, 5.2148895, 5.331615, 5.4408045, 5.5433717, 5.6400757, 5.7315493, 5.81833, 5.9008756, 5.9795804, 6.0547857, 6.1267905, 6.1958556, 6.2622104, 6.3260617, 6.3875904, 6.4469595, 6.5043163, 6.559791, 6.6135054, 6.665567, 6.716073, 6.7651157, 6.8127766, 6.8591313, 6.90425, 6.948197, 6.9910307, 7.032807, 7.0735765, 7.1133866, 7.1522813, 7.190302, 7.227487, 7.263872, 7.2994914, 7.334376, 7.368556, 7.4020596, 7.4349117, 7.4671383, 7.4987626, 7.5298066, 7.5602913, 7.590236, 7.6196604, 7.648582, 7.677017, 7.704982, 7.7324924, 7.759562], [0.0, 0.75, 1.929472, 2.6194189, 3.108944, 3.4886491, 3.7988908, 4.0611963, 4.288416, 4.4888377, 4.6681213, 4.8303027, 4.978363, 5.114565, 5.240669, 5.3580685, 5.467888, 5.571048, 5.66831, 5.7603116, 5.8475933, 5.930616, 6.009775, 6.085415, 6.157835, 6.2272983, 6.294037, 6.358257, 6.4201407, 6.4798527, 6.53754, 6.593336, 6.64736, 6.699722, 6.75052, 6.7998457, 6.8477817, 6.894404, 6.939783, 6.983984, 7.0270653, 7.0690823, 7.1100874, 7.1501274, 7.1892467, 7.227487, 7.264887, 7.3014817, 7.337307, 7.3723927, 7.4067707, 7.440467, 7.473509, 7.505922, 7.537729, 7.568952, 7.599612, 7.62973, 7.659324, 7.688413, 7.7170124, 7.745138, 7.7728076, 7.800034], [0.0, 0.75, 1.9360973, 2.6299195, 3.1221945, 3.5040326, 3.816017, 4.079796, 4.308292, 4.509839, 4.69013, 4.853223, 5.0021143, 5.1390815, 5.2658935, 5.383952, 5.494389, 5.5981283, 5.6959367, 5.788455, 5.876227, 5.959716, 6.03932, 6.1153846, 6.1882114, 6.258065, 6.3251786, 6.389759, 6.4519906, 6.5120378, 6.57005, 6.6261587, 6.6804867, 6.7331424, 6.784226, 6.833829, 6.882034, 6.9289184, 6.9745526, 7.019001, 7.0623245, 7.104578, 7.145813, 7.186078, 7.225417, 7.263872, 7.3014817, 7.3382826, 7.3743086, 7.4095917, 7.4441624, 7.4780483, 7.511276, 7.543871, 7.5758567, 7.6072555, 7.6380873, 7.668375, 7.698135, 7.727387, 7.756147, 7.784431, 7.812256, 7.8396354], [0.0, 0.75, 1.9425832, 2.6401997, 3.1351664, 3.5190923, 3.8327827, 4.0980043, 4.3277493, 4.5303993, 4.7116756, 4.87566, 5.025366, 5.1630816, 5.2905874, 5.409292, 5.520333, 5.6246395, 5.7229824, 5.8160067, 5.9042587, 5.9882035, 6.068243, 6.144724, 6.217949, 6.2881846, 6.355665, 6.420599, 6.4831705, 6.543546, 6.6018753, 6.658291, 6.7129154, 6.7658596, 6.8172226, 6.867097, 6.9155655, 6.962706, 7.0085897, 7.053282, 7.096842, 7.139326, 7.180787, 7.221272, 7.260826, 7.2994914, 7.337307, 7.3743086, 7.410532, 7.446008, 7.480768, 7.5148387, 7.5482483, 7.5810213, 7.613182, 7.6447525, 7.675753, 7.7062063, 7.7361293, 7.765541, 7.7944584, 7.822897, 7.850874, 7.8784027], [0.0, 0.75, 1.9489352, 2.6502671, 3.1478703, 3.5338414, 3.8492024, 4.115836, 4.3468056, 4.5505342, 4.7327766, 4.8976345, 5.0481377, 5.1865873, 5.3147717, 5.4341087, 5.5457406, 5.650603, 5.7494698, 5.8429894, 5.9317117, 6.0161037, 6.0965695, 6.1734576, 6.2470727, 6.3176827, 6.385523, 6.4508023, 6.513707, 6.5744042, 6.633044, 6.68976, 6.744676, 6.797902, 6.8495383, 6.899678, 6.948405, 6.9957967, 7.041925, 7.086855, 7.130647, 7.1733575, 7.2150393, 7.2557397, 7.2955046, 7.334376, 7.3723927, 7.4095917, 7.446008, 7.481673, 7.5166183, 7.5508704, 7.584458, 7.6174054, 7.6497374, 7.6814756, 7.7126417, 7.743257, 7.7733397, 7.802908, 7.8319793, 7.8605695, 7.8886957, 7.916371], [0.0, 0.75, 1.955159, 2.660132, 3.160318, 3.5482926, 3.8652909, 4.1333094, 4.3654766, 4.570264, 4.7534513, 4.919165, 5.07045, 5.209618, 5.3384686, 5.4584246, 5.570636, 5.6760426, 5.775423, 5.869428, 5.9586105, 6.043441, 6.1243243, 6.2016115, 6.275609, 6.3465853, 6.4147773, 6.4803953, 6.543627, 6.604639, 6.6635838, 6.7205944, 6.775795, 6.829297, 6.8812013, 6.9316015, 6.9805818, 7.028219, 7.074587, 7.11975, 7.1637697, 7.2067018, 7.2486, 7.2895117, 7.3294835, 7.368556, 7.4067707, 7.4441624, 7.480768, 7.5166183, 7.5517445, 7.5861745, 7.619936, 7.653055, 7.6855545, 7.7174582, 7.7487855, 7.77956, 7.8097982, 7.8395205, 7.868742, 7.8974814, 7.925753, 7.9535728], [0.0, 0.75, 1.9612595, 2.6698008, 3.172519, 3.5624573, 3.8810606, 4.1504354, 4.3837786, 4.5896015, 4.773717, 4.9402695, 5.09232, 5.2321925, 5.361695, 5.4822583, 5.595038, 5.7009783, 5.8008614, 5.8953424, 5.9849763, 6.070236, 6.1515293, 6.2292075, 6.3035793, 6.3749146, 6.4434524, 6.5094028, 6.572954, 6.6342754, 6.6935177, 6.7508173, 6.8062973, 6.8600707, 6.9122376, 6.9628925, 7.012121, 7.0599995, 7.106602, 7.1519938, 7.196236, 7.2393856, 7.281496, 7.3226147, 7.362788, 7.4020596, 7.440467, 7.4780483, 7.5148387, 7.5508704, 7.5861745, 7.620779, 7.6547112, 7.687998, 7.720662, 7.752727, 7.7842135, 7.815143, 7.845535, 7.8754077, 7.9047775, 7.9336615, 7.9620767, 7.990037], [0.0, 0.75, 1.9672415, 2.6792822, 3.184483, 3.5763474, 3.8965235, 4.1672287, 4.401725, 4.6085644, 4.7935886, 4.9609637, 5.1137652, 5.2543287, 5.3844705, 5.5056295, 5.618966, 5.7254295, 5.8258057, 5.9207535, 6.0108304, 6.0965114, 6.1782055, 6.2562675, 6.331007, 6.4026947, 6.4715705, 6.5378466, 6.601712, 6.663336, 6.7228713, 6.780453, 6.8362074, 6.890246, 6.942671, 6.9935765, 7.043047, 7.0911627, 7.137995, 7.183611, 7.2280717, 7.271435, 7.313753, 7.355075, 7.395447, 7.4349117, 7.473509, 7.511276, 7.5482483, 7.584458, 7.619936, 7.6547112, 7.688812, 7.7222624, 7.755088, 7.787311, 7.818953, 7.8500357, 7.880577, 7.910597, 7.9401126, 7.969139, 7.997695, 8.025793], [0.0, 0.75, 1.9731096, 2.688583, 3.1962192, 3.5899725, 3.9116926, 4.1837025, 4.4193287, 4.627166, 4.813082, 4.981264, 5.134802, 5.2760434, 5.4068127, 5.5285554, 5.6424384, 5.749415, 5.850275, 5.9456806, 6.036192, 6.122286, 6.204374, 6.282812, 6.357912, 6.429945, 6.4991527, 6.5657487, 6.6299224, 6.691843, 6.751665, 6.809525, 6.865548, 6.919847, 6.9725246, 7.0236754, 7.0733852, 7.121732, 7.168791, 7.214626, 7.2593017, 7.3028736, 7.3453956, 7.3869166, 7.4274836, 7.4671383, 7.505922, 7.543871, 7.5810213, 7.6174054, 7.653055, 7.687998, 7.7222624, 7.755874, 7.7888584, 7.821237, 7.853031, 7.884264, 7.9149528, 7.9451175, 7.974775, 8.0039425, 8.032635, 8.060868], [0.0, 0.75, 1.978868, 2.6977098, 3.207736, 3.6033432, 3.9265778, 4.1998687, 4.436604, 4.6454196, 4.8322115, 5.001185, 5.155446, 5.297352, 5.4287367, 5.551053, 5.665472, 5.7729526, 5.8742876, 5.9701424, 6.061079, 6.1475787, 6.230053, 6.308861, 6.3843136, 6.4566865, 6.5262203, 6.5931296, 6.657605, 6.719817, 6.779921, 6.838053, 6.89434, 6.948895, 7.0018206, 7.053212, 7.1031556, 7.1517305, 7.1990104, 7.245062, 7.2899475, 7.3337245, 7.3764467, 7.4181633, 7.4589214, 7.4987626, 7.537729, 7.5758567, 7.613182, 7.6497374, 7.6855545, 7.720662, 7.755088, 7.7888584, 7.821997, 7.8545284, 7.8864727, 7.917852, 7.9486856, 7.978992, 8.008789, 8.038094, 8.066921, 8.095287], [0.0, 0.75, 1.9845208, 2.7066693, 3.2190416, 3.6164687, 3.9411898, 4.215738, 4.4535627, 4.6633387, 4.8509893, 5.0207405, 5.1757107, 5.3182697, 5.4502587, 5.5731378, 5.688083, 5.7960577, 5.8978596, 5.9941545, 6.0855103, 6.172407, 6.2552614, 6.334431, 6.4102316, 6.4829373, 6.55279, 6.6200075, 6.6847796, 6.747278, 6.8076587, 6.8660583, 6.922604, 6.9774094, 7.0305786, 7.0822067, 7.1323795, 7.181178, 7.2286754, 7.2749386, 7.320031, 7.3640094, 7.406928, 7.448837, 7.4897823, 7.5298066, 7.568952, 7.6072555, 7.6447525, 7.6814756, 7.7174582, 7.752727, 7.787311, 7.821237, 7.8545284, 7.887209, 7.9192996, 7.9508243, 7.9817986, 8.012245, 8.042179, 8.071618, 8.100579, 8.129076], [0.0, 0.75, 1.9900717, 2.715467, 3.2301433, 3.629357, 3.9555387, 4.231321, 4.470215, 4.680934, 4.8694286, 5.0399427, 5.19561, 5.33881, 5.4713926, 5.594824, 5.7102866, 5.8187466, 5.9210057, 6.017734, 6.1095004, 6.1967883, 6.2800145, 6.359541, 6.435682, 6.508714, 6.5788817, 6.646401, 6.7114644, 6.7742443, 6.8348956, 6.893558, 6.950358, 7.00541, 7.058818, 7.110678, 7.1610775, 7.210095, 7.257806, 7.304277, 7.3495717, 7.393748, 7.4368596, 7.4789567, 7.5200863, 7.5602913, 7.599612, 7.6380873, 7.675753, 7.7126417, 7.7487855, 7.7842135, 7.818953, 7.853031, 7.8864727, 7.9192996, 7.9515357, 7.983201, 8.014315, 8.044899, 8.074967, 8.104539, 8.13363, 8.162254], [0.0, 0.75, 1.9955243, 2.7241092, 3.2410486, 3.6420178, 3.9696336, 4.2466288, 4.4865727, 4.6982183, 4.887542, 5.058806, 5.215158, 5.3589873, 5.492153, 5.616127, 5.732097, 5.841034, 5.9437428, 6.0408964, 6.133066, 6.220738, 6.3043303, 6.3842063, 6.460682, 6.5340357, 6.6045117, 6.6723275, 6.737677, 6.800733, 6.8616514, 6.920572, 6.977621, 7.032915, 7.0865583, 7.1386466, 7.189267, 7.2385006, 7.286421, 7.3330965, 7.3785906, 7.4229608, 7.4662623, 7.5085444, 7.5498548, 7.590236, 7.62973, 7.668375, 7.7062063, 7.743257, 7.77956, 7.815143, 7.8500357, 7.884264, 7.917852, 7.9508243, 7.983201, 8.015006, 8.046257, 8.076975, 8.107176, 8.136877, 8.166096, 8.194847], [0.0, 0.75, 2.0008821, 2.7326012, 3.251764, 3.654458, 3.9834833, 4.26167, 4.5026464, 4.7152023, 4.90534, 5.077341, 5.2343655, 5.3788133, 5.5125523, 5.6370597, 5.753528, 5.8629336, 5.9660845, 6.0636563, 6.1562223, 6.2442713, 6.3282228, 6.408442, 6.485247, 6.558916, 6.6296954, 6.6978035, 6.763434, 6.8267612, 6.8879414, 6.947115, 7.0044103, 7.0599422, 7.113816, 7.166128, 7.2169666, 7.2664113, 7.3145385, 7.3614144, 7.407104, 7.4516654, 7.4951534, 7.537617, 7.579105, 7.6196604, 7.659324, 7.698135, 7.7361293, 7.7733397, 7.8097982, 7.845535, 7.880577, 7.9149528, 7.9486856, 7.9817986, 8.014315, 8.046257, 8.077642, 8.108492, 8.1388235, 8.1686535, 8.197997, 8.2268715], [0.0, 0.75, 2.0061483, 2.740948, 3.2622967, 3.666686, 3.997096, 4.276454, 4.518445, 4.731896, 4.9228344, 5.095559, 5.2532444, 5.398301, 5.5326023, 5.657634, 5.7745934, 5.8844595, 5.9880443, 6.086027, 6.1789827, 6.267402, 6.3517075, 6.4322643, 6.5093927, 6.583372, 6.654449, 6.722844, 6.788751, 6.8523445, 6.913782, 6.973205, 7.030741, 7.0865073, 7.140608, 7.19314, 7.244192, 7.2938457, 7.342175, 7.389249, 7.435131, 7.4798794, 7.52355, 7.5661936, 7.607856, 7.648582, 7.688413, 7.727387, 7.765541, 7.802908, 7.8395205, 7.8754077, 7.910597, 7.9451175, 7.978992, 8.012245, 8.044899, 8.076975, 8.108492, 8.139472, 8.16993, 8.199884, 8.229353, 8.25835], [0.0, 0.75, 2.0113258, 2.7491546, 3.272652, 3.678708, 4.0104804, 4.29099, 4.533978, 4.748309, 4.9400344, 5.113471, 5.2718067, 5.4174604, 5.5523157, 5.6778626, 5.795304, 5.905623, 6.0096345, 6.1080213, 6.20136, 6.290144, 6.374797, 6.4556866, 6.5331326, 6.607416, 6.6787863, 6.747463, 6.813642, 6.877497, 6.9391885, 6.9988565, 7.05663, 7.1126256, 7.166949, 7.219698, 7.270961, 7.3208184, 7.369347, 7.416615, 7.4626865, 7.5076194, 7.55147, 7.594289, 7.6361227, 7.677017, 7.7170124, 7.756147, 7.7944584, 7.8319793, 7.868742, 7.9047775, 7.9401126, 7.974775, 8.008789, 8.042179, 8.074967, 8.107176, 8.1388235, 8.16993, 8.200515, 8.230593, 8.260183, 8.289298], [0.0, 0.75, 2.016418, 2.757225, 3.282836, 3.6905317, 4.023643, 4.3052845, 4.5492544, 4.76445, 4.956949, 5.1310863, 5.290061, 5.4363036, 5.571703, 5.697757, 5.815672, 5.9264364, 6.030868, 6.129652, 6.2233677, 6.31251, 6.397505, 6.47872, 6.556479, 6.6310635, 6.7027216, 6.771675, 6.838121, 6.9022346, 6.9641747, 7.0240836, 7.0820904, 7.1383114, 7.1928544, 7.2458167, 7.297286, 7.3473454, 7.39607, 7.4435287, 7.4897857, 7.5349, 7.578928, 7.6219196, 7.663923, 7.704982, 7.745138, 7.784431, 7.822897, 7.8605695, 7.8974814, 7.9336615, 7.969139, 8.0039425, 8.038094, 8.071618, 8.104539, 8.136877, 8.1686535, 8.199884, 8.230593, 8.260792, 8.290501, 8.319735], [0.0, 0.75, 2.0214274, 2.7651646, 3.2928548, 3.7021627, 4.036592, 4.319348, 4.564282, 4.780329, 4.9735904, 5.148416, 5.3080196, 5.4548397, 5.5907755, 5.7173276, 5.8357096, 5.9469123, 6.0517564, 6.150931, 6.2450175, 6.3345127, 6.4198437, 6.5013804, 6.579447, 6.6543255, 6.7262673, 6.795494, 6.8622026, 6.9265695, 6.988755, 7.0489006, 7.1071367, 7.1635804, 7.2183394, 7.2715106, 7.323184, 7.373441, 7.4223585, 7.4700046, 7.516445, 7.561738, 7.60594, 7.6491017, 7.691271, 7.7324924, 7.7728076, 7.812256, 7.850874, 7.8886957, 7.925753, 7.9620767, 7.997695, 8.032635, 8.066921, 8.100579, 8.13363, 8.166096, 8.197997, 8.229353, 8.260183, 8.290501, 8.320328, 8.349677], [0.0, 0.75, 2.0263565, 2.772977, 3.302713, 3.713608, 4.0493336, 4.333185, 4.579069, 4.795954, 4.9899645, 5.165468, 5.32569, 5.47308, 5.6095424, 5.736585, 5.855426, 5.9670596, 6.072311, 6.1718693, 6.266321, 6.356163, 6.4418244, 6.5236773, 6.6020465, 6.677216, 6.749437, 6.8189316, 6.8858986, 6.9505153, 7.0129414, 7.0733204, 7.1317825, 7.188445, 7.2434163, 7.2967935, 7.348667, 7.3991194, 7.448226, 7.4960575, 7.5426774, 7.588146, 7.6325197, 7.675848, 7.718181, 7.759562, 7.800034, 7.8396354, 7.8784027, 7.916371, 7.9535728, 7.990037, 8.025793, 8.060868, 8.095287, 8.129076, 8.162254, 8.194847, 8.2268715, 8.25835, 8.289298, 8.319735, 8.349677, 8.379139]];
\ No newline at end of file
+use crate::search::tunables::*;
+
+/*
+Late move reduction tables, generated at startup from the lmr tunables
+reduction = base + ln(depth) * ln(moves searched) / divisor, with base and divisor in hundredths
+Indexed by [is pv][is quiet][depth][moves searched]
+*/
+
+const SIZE: usize = 64;
+
+//how much of the table the lmr command prints
+const PRINT_DEPTH: usize = 16;
+const PRINT_MOVES: usize = 32;
+
+pub struct LMRTable {
+	table: Box<[[[[f32; SIZE]; SIZE]; 2]; 2]>
+}
+
+impl LMRTable {
+	pub fn new() -> LMRTable {
+		let mut table = Box::new([[[[0.0; SIZE]; SIZE]; 2]; 2]);
+
+		for is_pv in 0..2 {
+			for is_quiet in 0..2 {
+				let (base, divisor) = Self::coefficients(is_pv == 1, is_quiet == 1);
+
+				for depth in 1..SIZE {
+					for moves in 1..SIZE {
+						table[is_pv][is_quiet][depth][moves] = base + (depth as f32).ln() * (moves as f32).ln() / divisor;
+					}
+				}
+			}
+		}
+
+		LMRTable {
+			table
+		}
+	}
+
+	fn coefficients(is_pv: bool, is_quiet: bool) -> (f32, f32) {
+		let (base, divisor) = match (is_pv, is_quiet) {
+			(false, true) => (lmr_base_quiet(), lmr_divisor_quiet()),
+			(false, false) => (lmr_base_noisy(), lmr_divisor_noisy()),
+			(true, true) => (lmr_base_pv_quiet(), lmr_divisor_pv_quiet()),
+			(true, false) => (lmr_base_pv_noisy(), lmr_divisor_pv_noisy())
+		};
+
+		(base as f32 / 100.0, divisor as f32 / 100.0)
+	}
+
+	pub fn get(&self, is_pv: bool, is_quiet: bool, depth: i32, moves_searched: i32) -> i32 {
+		let depth = usize::min(depth as usize, SIZE - 1);
+		let moves_searched = usize::min(moves_searched as usize, SIZE - 1);
+
+		self.table[is_pv as usize][is_quiet as usize][depth][moves_searched] as i32
+	}
+
+	//prints the whole number reductions the search actually applies
+	pub fn print(&self) {
+		for is_pv in [false, true] {
+			for is_quiet in [true, false] {
+				let (base, divisor) = Self::coefficients(is_pv, is_quiet);
+				println!("{} {} (base {:.2}, divisor {:.2}), rows are depth and columns are moves searched:",
+					if is_pv { "PV" } else { "Non-PV" },
+					if is_quiet { "quiet" } else { "noisy" },
+					base,
+					divisor
+				);
+
+				for depth in 1..=PRINT_DEPTH {
+					let row: Vec<String> = (1..=PRINT_MOVES).map(|moves| format!("{:2}", self.get(is_pv, is_quiet, depth as i32, moves as i32))).collect();
+					println!("{:2} | {}", depth, row.join(" "));
+				}
+
+				println!();
+			}
+		}
+	}
+}
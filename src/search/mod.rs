@@ -1,4 +1,8 @@
 pub mod search_master;
 pub mod searcher;
 pub mod tt;
-pub mod lmr_table;
\ No newline at end of file
+pub mod lmr_table;
+pub mod tunables;
+pub mod time_manager;
+pub mod clock;
+pub mod stats;
\ No newline at end of file
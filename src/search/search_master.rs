@@ -1,36 +1,108 @@
 use cozy_chess::*;
 
 use std::thread;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
-use std::time::{Instant, Duration};
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Barrier, Mutex};
 
 use crate::search::tt::*;
+use crate::search::lmr_table::*;
 use crate::eval::score::*;
 use crate::search::searcher::*;
+use crate::search::stats::*;
 use crate::movegen::boardwrapper::*;
 use crate::movegen::movegen::*;
-use crate::movegen::boardwrapper::*;
 use crate::uci::castle_parse::*;
 
+//search threads recurse deeply and carry their move ordering tables on the stack
+const SEARCH_STACK_SIZE: usize = 16 * 1024 * 1024;
+
+//the last completed iteration of a search thread
+#[derive(Clone, Copy)]
+pub struct ThreadResult {
+	pub best_move: Option<Move>,
+	pub ponder_move: Option<Move>,
+	pub depth: i32,
+	pub score: i32
+}
+
+impl ThreadResult {
+	pub fn new() -> ThreadResult {
+		ThreadResult {
+			best_move: None,
+			ponder_move: None,
+			depth: 0,
+			score: -i32::MAX
+		}
+	}
+}
+
 pub struct SharedInfo<'a> {
 	pub tt: &'a TT,
-	pub best_move: Arc<Mutex<Option<Move>>>,
-	pub best_depth: Arc<Mutex<i32>>,
-	pub best_eval: Arc<Mutex<i32>>
+	//one private TT per thread in deterministic mode, empty otherwise
+	pub thread_tts: &'a [TT],
+	pub deterministic: bool,
+	//threads meet here after every iteration in deterministic mode
+	pub barrier: Barrier,
+	pub lmr_table: &'a LMRTable,
+	pub results: Vec<Mutex<ThreadResult>>,
+	pub stop: AtomicBool,
+	pub multipv: usize,
+	pub print_info: bool
 }
 
 impl SharedInfo<'_> {
-	pub fn new(tt: &TT) -> SharedInfo {
+	pub fn new<'a>(tt: &'a TT, thread_tts: &'a [TT], lmr_table: &'a LMRTable, thread_count: u32, multipv: usize, print_info: bool) -> SharedInfo<'a> {
 		SharedInfo {
-			tt: tt,
-			best_move: Arc::new(Mutex::new(None)),
-			best_depth: Arc::new(Mutex::new(0)),
-			best_eval: Arc::new(Mutex::new(i32::MIN))
+			tt,
+			thread_tts,
+			deterministic: !thread_tts.is_empty(),
+			barrier: Barrier::new(thread_count as usize),
+			lmr_table,
+			results: (0..thread_count).map(|_| Mutex::new(ThreadResult::new())).collect(),
+			stop: AtomicBool::new(false),
+			multipv,
+			print_info
 		}
 	}
 }
 
+//Stockfish style voting, every thread votes for its move weighted by its depth and how its score compares to the others
+//https://github.com/official-stockfish/Stockfish/blob/master/src/thread.cpp
+fn select_best_result(results: &[ThreadResult]) -> ThreadResult {
+	let finished: Vec<&ThreadResult> = results.iter().filter(|result| result.best_move.is_some()).collect();
+	if finished.is_empty() {
+		return results[0];
+	}
+
+	let min_score = finished.iter().map(|result| result.score).min().unwrap();
+	let mut votes: Vec<(Move, i64)> = Vec::new();
+
+	for result in &finished {
+		let weight = (result.score - min_score + Engine::VOTE_BASE) as i64 * result.depth as i64;
+
+		match votes.iter_mut().find(|(mv, _)| Some(*mv) == result.best_move) {
+			Some((_, count)) => *count += weight,
+			None => votes.push((result.best_move.unwrap(), weight))
+		}
+	}
+
+	let votes_for = |result: &ThreadResult| votes.iter().find(|(mv, _)| Some(*mv) == result.best_move).unwrap().1;
+
+	let mut best = *finished[0];
+	for &&result in &finished[1..] {
+		if best.score >= Score::MATE_THRESHOLD {
+			//a found mate is only replaced by a faster one
+			if result.score > best.score {
+				best = result;
+			}
+		} else if result.score >= Score::MATE_THRESHOLD || votes_for(&result) > votes_for(&best) {
+			best = result;
+		}
+	}
+
+	best
+}
+
 pub struct EngineThread<'a> {
 	pub shared_info: Option<&'a SharedInfo<'a>>,
 	movegen: MoveGen
@@ -54,6 +126,10 @@ pub struct TimeControl {
 	pub binc: i64,
 	pub movetime: Option<i64>,
 	pub movestogo: Option<i64>,
+	pub nodes: Option<u64>,
+	pub move_overhead: i64,
+	//time is measured in nodes searched at this speed instead of on the wall clock
+	pub simulated_nps: Option<u64>
 }
 
 impl TimeControl {
@@ -66,6 +142,9 @@ impl TimeControl {
 			binc: 0,
 			movetime: None,
 			movestogo: None,
+			nodes: None,
+			move_overhead: 0,
+			simulated_nps: None
 		}
 	}
 }
@@ -74,10 +153,18 @@ pub struct Engine<'a> {
 	pub boardwrapper: BoardWrapper,
 	pub my_past_positions: Vec<u64>,
 	pub nodes: u64,
+	pub best_move: Option<Move>,
+	pub score: i32,
+	pub stats: SearchStats,
+	pub print_info: bool,
+	pub multipv: usize,
+	hash: u32,
 	thread_count: u32,
 	threads: Vec<EngineThread<'a>>,
 	handler: Option<Arc<AtomicBool>>,
-	tt: TT
+	tt: TT,
+	thread_tts: Vec<TT>,
+	lmr_table: LMRTable
 }
 
 impl Engine<'_> {
@@ -86,54 +173,39 @@ impl Engine<'_> {
 			boardwrapper: BoardWrapper::new(),
 			my_past_positions: Vec::with_capacity(64),
 			nodes: 0,
-			thread_count: thread_count,
+			best_move: None,
+			score: 0,
+			stats: SearchStats::new(),
+			print_info: true,
+			multipv: 1,
+			hash,
+			thread_count,
 			threads: (0..thread_count).map(|_| EngineThread::new(None)).collect(),
 			handler: None,
-			tt: TT::new(hash)
+			tt: TT::new(hash),
+			thread_tts: Vec::new(),
+			lmr_table: LMRTable::new()
 		}
 	}
 
+	//deterministic mode gives every thread its own TT, splitting the hash between them, and searches the iterations in lockstep
+	//the same position and limits then always give the same move and node count for a fixed Threads and Hash
+	//the TT size of each thread depends on both, so changing either changes the search
+	pub fn set_deterministic(&mut self, deterministic: bool) {
+		self.thread_tts = if deterministic {
+			(0..self.thread_count).map(|_| TT::new((self.hash / self.thread_count).max(1))).collect()
+		} else {
+			Vec::new()
+		};
+	}
+
 	pub fn go(&mut self, time_control: TimeControl, handler: Arc<AtomicBool>) -> String {
-		let shared_info = SharedInfo::new(&self.tt);
-
-		//manage time
-		let mut time: u64;
-		let mut timeinc: u64;
-
-		let abort = handler.clone();
-
-		let movetime = time_control.movetime;
-		let movestogo = time_control.movestogo;
-
-		//set time
-		match self.boardwrapper.board.side_to_move() {
-			Color::White => {
-				time = time_control.wtime as u64;
-				timeinc = time_control.winc as u64;
-			},
-			Color::Black => {
-				time = time_control.btime as u64;
-				timeinc = time_control.binc as u64;	
-			}
+		self.tt.new_search();
+		for tt in &mut self.thread_tts {
+			tt.new_search();
 		}
 
-		if time != u64::MAX {
-			thread::spawn(move || {
-				let hard_timeout = if movetime.is_none() {
-					let mut hard_timeout_div = 2;
-					if let Some(movestogo) = movestogo {
-						hard_timeout_div /= movestogo / 10;
-					}
-
-					(time + timeinc) / (hard_timeout_div) as u64
-				} else {
-					movetime.unwrap() as u64
-				};
-
-				thread::sleep(Duration::from_millis(hard_timeout));
-				abort.store(true, Ordering::Relaxed);
-			});
-		}
+		let shared_info = SharedInfo::new(&self.tt, &self.thread_tts, &self.lmr_table, self.thread_count, self.multipv, self.print_info);
 
 		thread::scope(|scope| {
 			self.handler = Some(handler.clone());
@@ -146,30 +218,94 @@ impl Engine<'_> {
 				let positions = self.my_past_positions.clone();
 				let this_handler = &self.handler;
 				let this_shared_info = &shared_info;
-				let this_total_thread_count = self.thread_count;
 
-				worker_threads.push(scope.spawn(move || {
+				worker_threads.push(thread::Builder::new().stack_size(SEARCH_STACK_SIZE).spawn_scoped(scope, move || {
 					Searcher::create(time_control.clone(), 
 						this_shared_info, 
 						thread_movegen, 
 						boardwrapper, 
 						positions, 
 						this_handler.clone(),
-						this_total_thread_count)
-				}));
+						i as usize)
+				}).unwrap());
 			}
 
 			self.nodes = 0;
+			self.stats = SearchStats::new();
 			let mut index = 0;
 			for worker in worker_threads {
-				let (movegen, nodes) = worker.join().unwrap();
+				let (movegen, nodes, stats) = worker.join().unwrap();
 				self.threads[index].movegen = movegen.clone();
 				self.nodes += nodes;
+				self.stats.merge(&stats);
 				index += 1;
 			}
 
-			let best_move = *(&shared_info).best_move.lock().unwrap();
-			_960_to_regular_(best_move, &self.boardwrapper.board)
+			if SearchStats::ENABLED && self.print_info {
+				println!("info string {}", self.stats.report());
+			}
+
+			let results: Vec<ThreadResult> = shared_info.results.iter().map(|result| *result.lock().unwrap()).collect();
+			let best = select_best_result(&results);
+
+			let best_move = best.best_move;
+			self.best_move = best_move;
+			self.score = best.score;
+
+			//only the main thread prints its PV, so the ponder move is taken from it and left out when the vote picked another move
+			let ponder_move = if best_move.is_some() && results[0].best_move == best_move { results[0].ponder_move } else { None };
+
+			let mut bestmove_str = _960_to_regular_(best_move, &self.boardwrapper.board);
+			if let Some(ponder_move) = ponder_move {
+				let mut board = self.boardwrapper.board.clone();
+				board.play_unchecked(best_move.unwrap());
+				bestmove_str += &format!(" ponder {}", _960_to_regular_(Some(ponder_move), &board));
+			}
+
+			bestmove_str
 		})
 	}
+
+	//static eval and quiescence score of the current position, used to filter out tactical positions
+	//a disabled TT keeps search scores of this position from leaking into the quiescence result
+	pub fn qsearch(&mut self) -> (i32, i32) {
+		let tt = TT::new(0);
+		let shared_info = SharedInfo::new(&tt, &[], &self.lmr_table, 1, 1, false);
+		let mut searcher = Searcher::new(TimeControl::new(), &shared_info, self.threads[0].movegen.clone(), self.boardwrapper.clone(), self.my_past_positions.clone(), 0);
+
+		searcher.qsearch_root()
+	}
+}
+
+impl Engine<'_> {
+	//keeps the lowest scoring thread's vote above zero
+	const VOTE_BASE: i32 = 14;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	//later MultiPV lines search without the best move, so they must not replace it as the root TT move
+	#[test]
+	fn multipv_keeps_best_move_in_tt() {
+		//the engine and its move ordering tables need a search sized stack
+		thread::Builder::new().stack_size(SEARCH_STACK_SIZE).spawn(|| {
+			for fen in ["r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3", "r4rk1/p4ppp/1q2p3/2n1P3/2p5/3bRNP1/1P3PBP/R2Q2K1 b - - 0 24"] {
+				let board = Board::from_fen(fen, false).unwrap();
+
+				let mut engine = Engine::new(16, 1);
+				engine.print_info = false;
+				engine.multipv = 3;
+				engine.boardwrapper.set_board(board.clone());
+
+				let mut time_control = TimeControl::new();
+				time_control.depth = 8;
+				engine.go(time_control, Arc::new(AtomicBool::new(false)));
+
+				let entry = engine.tt.find(&board, 0).unwrap();
+				assert_eq!(entry.best_move, engine.best_move);
+			}
+		}).unwrap().join().unwrap();
+	}
 }
\ No newline at end of file
@@ -1,17 +1,20 @@
 use cozy_chess::*;
 
-use std::time::Instant;
+use std::mem;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-use crate::search::lmr_table::*;
-use crate::eval::evaluator::*;
+use crate::search::tunables::*;
+use crate::search::time_manager::*;
+use crate::search::stats::*;
 use crate::eval::score::*;
 use crate::search::tt::*;
 use crate::search::search_master::*;
 use crate::movegen::movesorter::*;
 use crate::movegen::movegen::*;
+use crate::movegen::movepicker::*;
 use crate::movegen::boardwrapper::*;
+use crate::uci::castle_parse::*;
 
 pub struct SearchInfo {
 	pub boardwrapper: BoardWrapper,
@@ -23,173 +26,373 @@ pub struct SearchInfo {
 
 pub struct Searcher<'a> {
 	pub time_control: TimeControl,
+	time_manager: TimeManager,
+	completed_depth: i32,
 	pub shared_info: &'a SharedInfo<'a>,
+	//the shared TT, or this thread's own in deterministic mode
+	tt: &'a TT,
 	pub movegen: MoveGen,
-	total_thread_count: u32,
+	thread_id: usize,
 	nodes: u64,
 	boardwrapper: BoardWrapper,
 	my_past_positions: Vec<u64>,
-	evals: [i32; 300]
+	evals: [i32; 300],
+	//triangular PV table, row ply holds the PV from ply onwards
+	pv_table: Vec<[Option<Move>; MAX_PLY]>,
+	pv_length: [usize; MAX_PLY],
+	//double extensions made along the current line
+	double_extensions: [i32; MAX_PLY],
+	//the move played at each ply, None for null moves, keying the continuation histories
+	move_stack: [Option<PieceTo>; MAX_PLY],
+	//move lists reused by every node at the same ply
+	move_buffers: Vec<Vec<SortedMove>>,
+	//moves of earlier MultiPV lines, skipped at the root
+	root_excluded: Vec<Move>,
+	//nodes spent below each root move, indexed by from and to
+	root_nodes: Box<[[u64; 64]; 64]>,
+	stats: SearchStats
 }
 
+const MAX_PLY: usize = 300;
+
 impl Searcher<'_> {
-	pub fn create(time_control: TimeControl, shared_info: &SharedInfo, movegen: MoveGen, boardwrapper: BoardWrapper, my_past_positions: Vec<u64>, handler: Option<Arc<AtomicBool>>, total_thread_count: u32) -> (MoveGen, u64) {
-		let mut instance = Searcher {
+	pub fn new<'a>(time_control: TimeControl, shared_info: &'a SharedInfo, mut movegen: MoveGen, boardwrapper: BoardWrapper, my_past_positions: Vec<u64>, thread_id: usize) -> Searcher<'a> {
+		//SEE counts material with the same piece weights as the evaluation
+		movegen.sorter.set_eval_params(&boardwrapper.eval_params);
+
+		Searcher {
 			time_control: time_control,
+			time_manager: TimeManager::new(&time_control, &boardwrapper.board),
+			completed_depth: 0,
 			shared_info: shared_info,
+			tt: shared_info.thread_tts.get(thread_id).unwrap_or(shared_info.tt),
 			movegen: movegen,
-			total_thread_count: total_thread_count,
+			thread_id,
 			nodes: 0,
 			boardwrapper: boardwrapper,
 			my_past_positions: my_past_positions,
-			evals: [0; 300]
-		};
+			evals: [0; 300],
+			pv_table: vec![[None; MAX_PLY]; MAX_PLY],
+			pv_length: [0; MAX_PLY],
+			double_extensions: [0; MAX_PLY],
+			move_stack: [None; MAX_PLY],
+			move_buffers: vec![Vec::new(); MAX_PLY],
+			root_excluded: Vec::new(),
+			root_nodes: Box::new([[0; 64]; 64]),
+			stats: SearchStats::new()
+		}
+	}
+
+	pub fn create(time_control: TimeControl, shared_info: &SharedInfo, movegen: MoveGen, boardwrapper: BoardWrapper, my_past_positions: Vec<u64>, handler: Option<Arc<AtomicBool>>, thread_id: usize) -> (MoveGen, u64, SearchStats) {
+		let mut instance = Searcher::new(time_control, shared_info, movegen, boardwrapper, my_past_positions, thread_id);
 
 		instance.go(handler.unwrap());
-		(instance.movegen, instance.nodes)
+		(instance.movegen, instance.nodes, instance.stats)
+	}
+
+	//returns the static eval and the quiescence score of the root position
+	pub fn qsearch_root(&mut self) -> (i32, i32) {
+		let boardwrapper = self.boardwrapper.clone();
+		let abort = AtomicBool::new(false);
+
+		let static_eval = self.static_eval(&boardwrapper, boardwrapper.evaluate());
+		let (_, eval) = self.qsearch(&abort, &boardwrapper, -i32::MAX, i32::MAX, 0, false).unwrap();
+
+		(static_eval, eval.score)
 	}
 
 	pub fn go(&mut self, handler: Arc<AtomicBool>) {
-		let now = Instant::now();
+		let is_main_thread = self.thread_id == 0;
 
 		let mut last_result = 0;
 		let mut depth_index = 0;
-		let mut window = 10;
+		let mut window = self.aspiration_start();
+
+		//helpers only search the best line
+		let mut root_moves = 0;
+		self.boardwrapper.board.generate_moves(|moves| {
+			root_moves += moves.len();
+			false
+		});
+		let multipv = if is_main_thread { usize::min(self.shared_info.multipv, root_moves).max(1) } else { 1 };
 
 		while depth_index < self.time_control.depth && depth_index < 250 {
-			let boardwrapper = &mut self.boardwrapper.clone();
-			let mut past_positions = self.my_past_positions.clone();
+			//soft node limit, checked before starting a new iteration
+			//in deterministic mode the main thread checks it for everyone when the iteration ends
+			if !self.shared_info.deterministic && self.node_limit_reached() {
+				break;
+			}
 
-			let new_alpha = if depth_index + 1 > 3 {
-				last_result - window
-			} else {
-				-i32::MAX
-			};
+			let mut stop = false;
 
-			let new_beta = if depth_index + 1 > 3 {
-				last_result + window
+			//helpers skip some depths so the threads spread out instead of searching the same tree
+			if !is_main_thread && self.skip_depth(depth_index + 1) {
+				depth_index += 1;
 			} else {
-				i32::MAX
-			};
+				let boardwrapper = &mut self.boardwrapper.clone();
+				let mut past_positions = self.my_past_positions.clone();
 
-			let search_handler: Arc<AtomicBool> = handler.clone();
+				let new_alpha = if depth_index + 1 > 3 {
+					last_result - window
+				} else {
+					-i32::MAX
+				};
 
-			let result = self.search(&search_handler, boardwrapper, depth_index + 1, 0, new_alpha, new_beta, &mut past_positions, None);
+				let new_beta = if depth_index + 1 > 3 {
+					last_result + window
+				} else {
+					i32::MAX
+				};
 
-			if result != None {
-				let (best_mv, eval) = result.unwrap();
+				let search_handler: Arc<AtomicBool> = handler.clone();
 
-				if eval.score <= last_result - window || eval.score >= last_result + window {
-					window *= 2;
-					continue;
-				}
+				let result = self.search(&search_handler, boardwrapper, depth_index + 1, 0, new_alpha, new_beta, &mut past_positions, None, None);
 
-				window = 10;
-				last_result = eval.score;
+				if let Some((best_mv, eval)) = result {
+					//only searches with an aspiration window can fail low or high
+					if depth_index + 1 > 3 && (eval.score <= last_result - window || eval.score >= last_result + window) {
+						window *= 2;
+						continue;
+					}
 
-				let mut best_move = self.shared_info.best_move.lock().unwrap();
-				let mut best_depth = self.shared_info.best_depth.lock().unwrap();
-				let mut best_eval = self.shared_info.best_eval.lock().unwrap();
+					window = self.aspiration_start();
+					last_result = eval.score;
 
-				depth_index += 1;
+					depth_index += 1;
 
-				let is_main_thread = depth_index > *best_depth || (depth_index == *best_depth && eval.score > *best_eval) || self.total_thread_count == 1;
+					let mut lines = vec![(eval.clone(), self.root_pv(best_mv))];
 
-				if is_main_thread {
-					*best_move = best_mv.clone();
-					*best_depth = depth_index;
-					*best_eval = eval.score;
-				}
-				
-				let mut time: u64;
-				let mut timeinc: u64;
+					//further MultiPV lines are searched with a full window, skipping the first moves of the earlier lines
+					while lines.len() < multipv {
+						self.root_excluded.push(lines[lines.len() - 1].1[0]);
 
-				let movetime = self.time_control.movetime;
-				let movestogo = self.time_control.movestogo;
-				
-				//set time
-				match self.boardwrapper.board.side_to_move() {
-					Color::White => {
-						time = self.time_control.wtime as u64;
-						timeinc = self.time_control.winc as u64;
-					},
-					Color::Black => {
-						time = self.time_control.btime as u64;
-						timeinc = self.time_control.binc as u64;	
-					}
-				}
+						let boardwrapper = &mut self.boardwrapper.clone();
+						let mut past_positions = self.my_past_positions.clone();
 
-				let elapsed: f32 = now.elapsed().as_secs_f32() * 1000_f32;
+						match self.search(&search_handler, boardwrapper, depth_index, 0, -i32::MAX, i32::MAX, &mut past_positions, None, None) {
+							Some((line_mv, line_eval)) => lines.push((line_eval, self.root_pv(line_mv))),
+							None => break
+						}
+					}
 
-				if time != u64::MAX {
-					let mut soft_timeout = None;
+					self.root_excluded.clear();
 
-					let mut soft_timeout_div = 25;
-					if let Some(movestogo) = movestogo {
-						soft_timeout_div /= movestogo / 10;
-					}
+					//publish the finished iteration for the vote
+					*self.shared_info.results[self.thread_id].lock().unwrap() = ThreadResult {
+						best_move: best_mv,
+						ponder_move: lines[0].1.get(1).copied(),
+						depth: depth_index,
+						score: eval.score
+					};
+					
+					self.completed_depth = depth_index;
+					let elapsed = self.time_manager.elapsed(self.nodes);
 
-					soft_timeout = Some((time + timeinc) / (soft_timeout_div) as u64);
+					//only the main thread decides when the search is over
+					let best_move_nodes = best_mv.map_or(0, |mv| self.root_nodes[mv.from as usize][mv.to as usize]);
+					stop = is_main_thread && self.time_manager.soft_stop(best_mv, eval.score, best_move_nodes, self.nodes);
 
-					if movetime.is_none() && !soft_timeout.is_none() {
-						if elapsed as u64 > soft_timeout.unwrap() {
-							break;
-						}
+					if is_main_thread && self.shared_info.print_info {
+						self.print_info(depth_index, elapsed, &lines, multipv);
 					}
+				} else {
+					stop = true;
 				}
+			}
 
-				if !is_main_thread {
-					continue;
-				}
+			if self.end_iteration(stop || (is_main_thread && self.node_limit_reached())) {
+				break;
+			}
+		}
 
-				//get nps
-				let mut nps: u64;
-				if elapsed == 0_f32 {
-					nps = self.nodes;
-				} else {
-					nps = ((self.nodes as f32 * 1000_f32) / elapsed) as u64;
-				}
+		//stop the helpers once the main thread is done
+		if is_main_thread {
+			self.shared_info.stop.store(true, Ordering::Relaxed);
+		}
+	}
 
-				let mut score_str = if eval.mate {
-					let mut mate_score = if eval.score > 0 {
-						(((Score::CHECKMATE_BASE - eval.score + 1) / 2) as f32).ceil()
-					} else {
-						((-(eval.score + Score::CHECKMATE_BASE) / 2) as f32).ceil()
-					};
+	fn node_limit_reached(&self) -> bool {
+		self.time_control.nodes.is_some_and(|nodes| self.nodes >= nodes)
+	}
 
-					format!("mate {}", mate_score)
-				} else {
-					format!("cp {}", eval.score)
-				};
+	//in deterministic mode every thread finishes the iteration before any of them learns whether to go on,
+	//so no thread ever sees another one mid-iteration
+	//a thread that aborted raises the stop flag, which ends the current iteration for the rest too
+	fn end_iteration(&self, stop: bool) -> bool {
+		if !self.shared_info.deterministic {
+			return stop;
+		}
+
+		if stop {
+			self.shared_info.stop.store(true, Ordering::Relaxed);
+		}
+
+		self.shared_info.barrier.wait();
+		let stop = self.shared_info.stop.load(Ordering::Relaxed);
 
-				println!("info depth {} time {} score {} nodes {} nps {} pv {}", depth_index, elapsed as u64, score_str, self.nodes, nps, self.get_pv(&mut boardwrapper.board, depth_index, 0));
+		//nobody starts the next iteration before everyone has read the decision
+		self.shared_info.barrier.wait();
+		stop
+	}
+
+	fn print_info(&self, depth: i32, elapsed: u64, lines: &[(Eval, Vec<Move>)], multipv: usize) {
+		//get nps
+		let nps = (self.nodes * 1000).checked_div(elapsed).unwrap_or(self.nodes);
+
+		for (index, (line_eval, pv)) in lines.iter().enumerate() {
+			let multipv_str = if multipv > 1 {
+				format!(" multipv {}", index + 1)
 			} else {
-				break;
-			}
+				String::new()
+			};
+
+			println!("info depth {}{} time {} score {} nodes {} nps {} pv {}", depth, multipv_str, elapsed, Self::score_string(line_eval), self.nodes, nps, self.pv_string(pv));
+		}
+	}
+
+	//the main thread polls the clock every TIME_CHECK_INTERVAL nodes and stops every thread once the hard limit is hit
+	//the first iteration always finishes so there is a move to play
+	fn should_abort(&self, abort: &AtomicBool) -> bool {
+		if abort.load(Ordering::Relaxed) || self.shared_info.stop.load(Ordering::Relaxed) {
+			return true;
+		}
+
+		if self.thread_id == 0 && self.completed_depth > 0 && self.nodes.is_multiple_of(TimeManager::TIME_CHECK_INTERVAL) && self.time_manager.hard_stop(self.nodes) {
+			self.shared_info.stop.store(true, Ordering::Relaxed);
+			return true;
+		}
+
+		false
+	}
+
+	//helpers start with wider aspiration windows
+	fn aspiration_start(&self) -> i32 {
+		aspiration_window() + (self.thread_id % 4) as i32 * Self::HELPER_WINDOW_STEP
+	}
+
+	//lazy SMP skip pattern, each helper skips a different set of depths
+	fn skip_depth(&self, depth: i32) -> bool {
+		let index = (self.thread_id - 1) % Self::SKIP_SIZE.len();
+		((depth + Self::SKIP_PHASE[index]) / Self::SKIP_SIZE[index]) % 2 != 0
+	}
+
+	//PV of the last root search, with the TT as a fallback when it is empty
+	fn root_pv(&self, best_mv: Option<Move>) -> Vec<Move> {
+		let pv: Vec<Move> = self.pv_table[0][..self.pv_length[0]].iter().map(|mv| mv.unwrap()).collect();
+
+		if !pv.is_empty() {
+			return pv;
 		}
+
+		let mut tt_pv = self.tt_pv(&mut self.boardwrapper.board.clone(), 0);
+		if tt_pv.first().copied() != best_mv {
+			tt_pv = best_mv.into_iter().collect();
+		}
+
+		tt_pv
 	}
 
 	//fish PV from TT
-	fn get_pv(&self, board: &mut Board, depth: i32, ply: i32) -> String {
-		if depth == 0 || ply > 50 {
-			return String::new();
+	fn tt_pv(&self, board: &mut Board, ply: i32) -> Vec<Move> {
+		if ply > 50 {
+			return Vec::new();
 		}
 
 		//probe TT
-		match self.shared_info.tt.find(board, ply) {
-			Some(table_find) => {
-				let mut pv = String::new();
-				if board.is_legal(table_find.best_move.unwrap()) {
-					board.play_unchecked(table_find.best_move.unwrap());
-					pv = format!("{} {}", table_find.best_move.unwrap(), self.get_pv(board, depth - 1, ply + 1));
-				}
+		if let Some(table_find) = self.tt.find(board, ply) {
+			if let Some(mv) = table_find.best_move.filter(|&mv| board.is_legal(mv)) {
+				board.play_unchecked(mv);
 
+				let mut pv = vec![mv];
+				pv.extend(self.tt_pv(board, ply + 1));
 				return pv;
-			},
-			None => {}
+			}
 		}
 
-		String::new()
+		Vec::new()
+	}
+
+	fn pv_string(&self, pv: &[Move]) -> String {
+		let mut board = self.boardwrapper.board.clone();
+		let mut pv_str = String::new();
+
+		for &mv in pv {
+			pv_str += &format!("{} ", _960_to_regular_(Some(mv), &board));
+			board.play_unchecked(mv);
+		}
+
+		pv_str
+	}
+
+	fn score_string(eval: &Eval) -> String {
+		if eval.mate {
+			let mut mate_score = if eval.score > 0 {
+				(((Score::CHECKMATE_BASE - eval.score + 1) / 2) as f32).ceil()
+			} else {
+				((-(eval.score + Score::CHECKMATE_BASE) / 2) as f32).ceil()
+			};
+
+			format!("mate {}", mate_score)
+		} else {
+			format!("cp {}", eval.score)
+		}
+	}
+
+	//the moves 1 and 2 plies back, for the continuation histories
+	fn continuations(&self, ply: i32) -> [Option<PieceTo>; 2] {
+		let ply = ply as usize;
+		[
+			if ply >= 1 { self.move_stack[ply - 1] } else { None },
+			if ply >= 2 { self.move_stack[ply - 2] } else { None }
+		]
+	}
+
+	//a node nested at the same ply, like a singular search, finds the slot empty and allocates its own list
+	fn take_move_buffer(&mut self, ply: i32) -> Vec<SortedMove> {
+		match self.move_buffers.get_mut(ply as usize) {
+			Some(buffer) => mem::take(buffer),
+			None => Vec::new()
+		}
+	}
+
+	fn return_move_buffer(&mut self, ply: i32, buffer: Vec<SortedMove>) {
+		if let Some(slot) = self.move_buffers.get_mut(ply as usize) {
+			*slot = buffer;
+		}
+	}
+
+	fn clear_pv(&mut self, ply: i32) {
+		if (ply as usize) < MAX_PLY {
+			self.pv_length[ply as usize] = ply as usize;
+		}
+	}
+
+	//makes mv followed by the child's PV the PV of this ply
+	fn update_pv(&mut self, ply: i32, mv: Move) {
+		let ply = ply as usize;
+		if ply + 1 >= MAX_PLY {
+			return;
+		}
+
+		let child_length = self.pv_length[ply + 1].max(ply + 1);
+		let (parents, children) = self.pv_table.split_at_mut(ply + 1);
+
+		parents[ply][ply] = Some(mv);
+		parents[ply][ply + 1..child_length].copy_from_slice(&children[0][ply + 1..child_length]);
+		self.pv_length[ply] = child_length;
+	}
+
+	//raw evaluation corrected by the correction histories
+	fn static_eval(&mut self, boardwrapper: &BoardWrapper, raw_eval: i32) -> i32 {
+		let base_eval = raw_eval as f32;
+		let pawn_corrhist = self.movegen.sorter.read_pawn_corrhist(boardwrapper);
+		let non_pawn_corrhist = self.movegen.sorter.read_non_pawn_corrhist(boardwrapper);
+		let material_corrhist = self.movegen.sorter.read_material_corrhist(boardwrapper);
+
+		(base_eval 
+			+ pawn_corrhist 
+			+ non_pawn_corrhist
+			+ material_corrhist) as i32
 	}
 
 	fn is_repetition(&self, board: &Board, past_positions: &mut Vec<u64>) -> bool {
@@ -205,20 +408,21 @@ impl Searcher<'_> {
 
 	fn get_nmp_reduction_amount(&self, depth: i32, diff: i32) -> i32 {
 		//calculate nmp reduction amount
-		return 2 + (depth / 3) + (diff / 128);
+		nmp_base() + (depth / nmp_depth_divisor()) + (diff / nmp_eval_divisor())
 	}
 
-	fn get_lmr_reduction_amount(&self, mut depth: i32, mut moves_searched: i32) -> i32 {
-		return LMR_TABLE[usize::min(depth as usize, 63)][usize::min(moves_searched as usize, 63)] as i32; 
+	fn get_lmr_reduction_amount(&self, is_pv: bool, is_quiet: bool, depth: i32, moves_searched: i32) -> i32 {
+		self.shared_info.lmr_table.get(is_pv, is_quiet, depth, moves_searched)
 	}
 
-	pub fn search(&mut self, abort: &AtomicBool, boardwrapper: &BoardWrapper, mut depth: i32, mut ply: i32, mut alpha: i32, mut beta: i32, past_positions: &mut Vec<u64>, last_move: Option<Move>) -> Option<(Option<Move>, Eval)> {		
+	pub fn search(&mut self, abort: &AtomicBool, boardwrapper: &BoardWrapper, mut depth: i32, mut ply: i32, mut alpha: i32, mut beta: i32, past_positions: &mut Vec<u64>, last_move: Option<Move>, excluded: Option<Move>) -> Option<(Option<Move>, Eval)> {		
 		//abort?
-		if self.time_control.depth > 1 && abort.load(Ordering::Relaxed) {
+		if self.time_control.depth > 1 && self.should_abort(abort) {
 			return None;
 		}
 
 		self.nodes += 1;
+		self.clear_pv(ply);
 
 		//MATE DISTANCE PRUNING
 		//make sure that alpha is not defaulted to negative infinity
@@ -229,6 +433,11 @@ impl Searcher<'_> {
 		let mut globally_extended = false;
 		let in_check = !boardwrapper.board.checkers().is_empty();
 		let is_pv = beta > alpha + 1;
+		let is_singular_search = excluded.is_some();
+
+		let line_double_extensions = if ply > 0 { self.double_extensions[ply as usize - 1] } else { 0 };
+		let conts = self.continuations(ply);
+		self.double_extensions[ply as usize] = line_double_extensions;
 
 		//CHECK EXTENSION
 		if in_check {
@@ -244,21 +453,26 @@ impl Searcher<'_> {
 		}
 
 		if depth <= 0 {
-			return self.qsearch(&abort, boardwrapper, alpha, beta, ply); //proceed with qSearch to avoid horizon effect
+			return self.qsearch(abort, boardwrapper, alpha, beta, ply, true); //proceed with qSearch to avoid horizon effect
 		}
 
+		self.stats.record(Stat::Nodes);
+
 		//check for three move repetition
 		if self.is_repetition(&boardwrapper.board, past_positions) && ply > 0 {
 			return Some((None, Eval::new(Score::DRAW, false)));
 		}
 
-		let mut legal_moves: Vec<SortedMove> = Vec::with_capacity(64);
-
 		//probe tt
-		let (tt_hit, iid) = match self.shared_info.tt.find(&boardwrapper.board, ply) {
+		self.stats.record(Stat::TTProbes);
+		let (tt_hit, iid) = match self.tt.find(&boardwrapper.board, ply) {
 			Some(table_find) => {
+				self.stats.record(Stat::TTHits);
+
 				//if sufficient depth
-				if table_find.depth >= depth {
+				//if NOT a PV node, so the PV table always holds the full line
+				//if NOT searching without the TT move, since the entry is about the full position
+				if !is_pv && !is_singular_search && table_find.depth >= depth {
 					//check if position from TT is a mate
 					let mut is_checkmate = if table_find.eval < -Score::CHECKMATE_BASE || table_find.eval > Score::CHECKMATE_BASE {
 						true
@@ -266,21 +480,16 @@ impl Searcher<'_> {
 						false
 					};
 
-					match table_find.node_kind {
-						NodeKind::Exact => {
-							return Some((table_find.best_move, Eval::new(table_find.eval, is_checkmate)));
-						},
-						NodeKind::UpperBound => {
-							if table_find.eval <= alpha {
-								return Some((table_find.best_move, Eval::new(table_find.eval, is_checkmate)));
-							}	
-						},
-						NodeKind::LowerBound => {
-							if table_find.eval >= beta {
-								return Some((table_find.best_move, Eval::new(table_find.eval, is_checkmate)));
-							}
-						},
-						NodeKind::Null => {}
+					let is_cutoff = match table_find.node_kind {
+						NodeKind::Exact => true,
+						NodeKind::UpperBound => table_find.eval <= alpha,
+						NodeKind::LowerBound => table_find.eval >= beta,
+						NodeKind::Null => false
+					};
+
+					if is_cutoff {
+						self.stats.record(Stat::TTCutoffs);
+						return Some((table_find.best_move, Eval::new(table_find.eval, is_checkmate)));
 					}
 				}
 
@@ -294,9 +503,10 @@ impl Searcher<'_> {
 
 				//if sufficient depth
 				//if PV node
-				if depth >= Self::IID_DEPTH_MIN	&& is_pv {
-					let (best_mv, _) = self.search(&abort, boardwrapper, depth - 10, ply, alpha, beta, past_positions, last_move)?;
+				if depth >= iid_depth_min()	&& is_pv {
+					let (best_mv, _) = self.search(abort, boardwrapper, depth - 10, ply, alpha, beta, past_positions, last_move, None)?;
 					iid_move = best_mv;
+					self.clear_pv(ply);
 				}
 
 				//Internal Iterative Reduction
@@ -310,34 +520,50 @@ impl Searcher<'_> {
 			}
 		};
 
-		//static eval for tuning methods
-		let static_eval = if tt_hit.as_ref().is_some() {
-			tt_hit.as_ref().unwrap().eval
-		} else {
-			let base_eval = evaluate(&boardwrapper.board) as f32;
-			let pawn_corrhist = self.movegen.sorter.read_pawn_corrhist(boardwrapper);
-			let non_pawn_corrhist = self.movegen.sorter.read_non_pawn_corrhist(boardwrapper);
-			let material_corrhist = self.movegen.sorter.read_material_corrhist(boardwrapper);
-
-			(base_eval 
-				+ pawn_corrhist 
-				+ non_pawn_corrhist
-				+ material_corrhist) as i32
+		//the TT saves us the evaluation, the corrections are always applied fresh
+		let raw_eval = match tt_hit.as_ref() {
+			Some(table_find) => table_find.static_eval,
+			None => boardwrapper.evaluate()
 		};
+		let static_eval = self.static_eval(boardwrapper, raw_eval);
 
 		self.evals[ply as usize] = static_eval;
 		let improving = ply > 1 && self.evals[ply as usize] > self.evals[ply as usize - 2];
 
 		//Reverse Futility Pruning
 		/*
+		// if NOT root node
 		// if depth isn't too deep
 		// if NOT in check
+		// if NOT verifying a singular move
 		// THEN prune
 		*/
 
-		if depth <= Self::MAX_DEPTH_RFP && !in_check {
-			if static_eval - (Self::MULTIPLIER_RFP * depth) - (!improving as i32 * 30) >= beta {
-				return Some((None, Eval::new(static_eval, false)));
+		if ply > 0 && depth <= rfp_depth_max() && !in_check && !is_singular_search
+		&& static_eval - (rfp_multiplier() * depth) - (!improving as i32 * rfp_improving_margin()) >= beta {
+			self.stats.record(Stat::Rfp);
+			return Some((None, Eval::new(static_eval, false)));
+		}
+
+		//Razoring
+		//At low depth a static eval far below alpha is unlikely to recover, so the node is resolved with qsearch
+		/*
+		// if NOT root node
+		// if NOT PV node
+		// if NOT in check
+		// if NOT verifying a singular move
+		// if low depth
+		// if alpha is NOT a mate score
+		// if static eval plus the margin can NOT reach alpha
+		// THEN drop into qsearch, and prune if it confirms the fail low
+		*/
+
+		if ply > 0 && !is_pv && !in_check && !is_singular_search && depth <= razor_depth_max() && alpha.abs() < Score::MATE_THRESHOLD && static_eval + razor_margin() * depth < alpha {
+			let (_, razor_eval) = self.qsearch(abort, boardwrapper, alpha, alpha + 1, ply, true)?;
+
+			if razor_eval.score <= alpha {
+				self.stats.record(Stat::Razoring);
+				return Some((None, razor_eval));
 			}
 		}
 
@@ -348,6 +574,7 @@ impl Searcher<'_> {
 		// if NOT in check
 		// if board has non pawn material
 		// if board can produce a beta cutoff
+		// if NOT verifying a singular move
 		// THEN prune
 		*/
 
@@ -359,75 +586,163 @@ impl Searcher<'_> {
 			true
 		};
 
-		if ply > 0 && !in_check && !(our_pieces & sliding_pieces).is_empty() && static_eval >= beta && improving_nmp_check {
+		if ply > 0 && !in_check && !is_singular_search && !(our_pieces & sliding_pieces).is_empty() && static_eval >= beta && improving_nmp_check {
+			self.stats.record(Stat::NMPTries);
 			let r = self.get_nmp_reduction_amount(depth, static_eval - beta + (!improving as i32) * 30);
 
 			let nulled_board = &boardwrapper.clone().null_move();
+			self.move_stack[ply as usize] = None;
 			
-			let (_, mut null_score) = self.search(&abort, nulled_board, depth - r, ply + 1, -beta, -beta + 1, past_positions, None)?; //perform a ZW search
+			let (_, mut null_score) = self.search(abort, nulled_board, depth - r, ply + 1, -beta, -beta + 1, past_positions, None, None)?; //perform a ZW search
 
 			null_score.score *= -1;
 		
 			if null_score.score >= beta {
+				self.stats.record(Stat::NMPCutoffs);
 				return Some((None, Eval::new(beta, false))); //return the lower bound produced by the fail high for this node since doing nothing in this position is insanely good
 			}
 		}
 
+		//ProbCut
+		//If a good capture beats beta by a margin at reduced depth, the full depth search would very likely fail high too
+		/*
+		// if NOT PV node
+		// if NOT in check
+		// if NOT verifying a singular move
+		// if sufficient depth
+		// if beta is NOT a mate score
+		// if the TT does not already tell us the capture search would fall short
+		// THEN try captures whose SEE reaches the raised beta
+		*/
+
+		let probcut_beta = beta + probcut_margin();
+		let tt_refutes_probcut = tt_hit.as_ref().is_some_and(|table_find| table_find.depth >= depth - 3 && table_find.eval < probcut_beta);
+
+		if !is_pv && !in_check && !is_singular_search && depth >= probcut_depth_min() && beta.abs() < Score::MATE_THRESHOLD && !tt_refutes_probcut {
+			let tt_move = tt_hit.as_ref().and_then(|table_find| table_find.best_move);
+			let mut picker = MovePicker::new_quiescence(self.take_move_buffer(ply), &boardwrapper.board, tt_move, ply, false);
+
+			while let Some(mut sm) = picker.next(&mut self.movegen.sorter, &boardwrapper.board) {
+				let mv = sm.mv;
+
+				if self.movegen.sorter.see(&boardwrapper.board, mv) < probcut_beta - static_eval {
+					continue;
+				}
+
+				let mut board_wrapper_cache = boardwrapper.clone();
+				board_wrapper_cache.play_unchecked(&mut sm);
+
+				self.move_stack[ply as usize] = Some(PieceTo::new(&boardwrapper.board, mv));
+				past_positions.push(board_wrapper_cache.board.hash());
+
+				//cheap qsearch verification first, then the reduced search
+				let (_, mut probcut_eval) = self.qsearch(abort, &board_wrapper_cache, -probcut_beta, -probcut_beta + 1, ply + 1, false)?;
+				probcut_eval.score *= -1;
+
+				if probcut_eval.score >= probcut_beta {
+					let (_, mut child_eval) = self.search(abort, &board_wrapper_cache, depth - probcut_reduction(), ply + 1, -probcut_beta, -probcut_beta + 1, past_positions, Some(mv), None)?;
+					child_eval.score *= -1;
+
+					probcut_eval = child_eval;
+				}
+
+				past_positions.pop();
+
+				if probcut_eval.score >= probcut_beta {
+					self.stats.record(Stat::ProbCut);
+					self.return_move_buffer(ply, picker.into_buffer());
+					self.tt.insert(Some(mv), probcut_eval.score, raw_eval, boardwrapper.board.hash(), ply, (depth - probcut_reduction() + 1).max(0), NodeKind::LowerBound);
+
+					return Some((Some(mv), probcut_eval));
+				}
+			}
+
+			self.return_move_buffer(ply, picker.into_buffer());
+		}
+
 		let mut best_move = None;
 		let mut best_move_type = None;
 		let mut eval = Eval::new(i32::MIN, false);
 
 		//STAGED MOVEGEN
-		//Check if TT moves produce a cutoff before generating moves to same time
-		let mut staged_movegen = tt_hit.is_some() || tt_hit.is_some();
-		if staged_movegen {
-			let mv = if tt_hit.is_some() {
-				tt_hit.clone().unwrap().best_move.unwrap()
-			} else {
-				iid.clone().unwrap()
-			};
-
-			let movetype = if (mv.to.bitboard() & boardwrapper.board.colors(!boardwrapper.board.side_to_move())).is_empty() {
-				MoveType::Quiet
-			} else {
-				MoveType::Loud
-			};
-			let mut sm = SortedMove::new(mv, 0, movetype);
-
-			legal_moves.push(sm);
-		} else {
-			legal_moves = self.movegen.move_gen(&boardwrapper.board, None, ply, false, last_move);
-		}
+		//The TT move is tried before any moves are generated, the rest are generated and scored lazily by the picker
+		let staged_move = tt_hit.as_ref().and_then(|table_find| table_find.best_move).or(iid).filter(|&mv| Some(mv) != excluded && (ply > 0 || !self.root_excluded.contains(&mv)));
+		let mut picker = MovePicker::new(self.take_move_buffer(ply), &boardwrapper.board, staged_move, ply, last_move, conts);
 
 		let mut moves_searched = 0;
-		let mut legal_index = 0;
 		let mut tt_nodetype = NodeKind::UpperBound;
 
-		while legal_index < legal_moves.len() {
-			let mut mvlen = legal_moves.len() as i32;
-			let mut sm = &mut legal_moves[legal_index];
+		while let Some(mut sm) = picker.next(&mut self.movegen.sorter, &boardwrapper.board) {
+			let mvlen = picker.move_count() as i32;
 			let mv = sm.mv;
+			let is_tt_move = picker.tt_move() == Some(mv);
+
+			//moves of earlier MultiPV lines are skipped at the root, and the singular move while verifying it
+			if Some(mv) == excluded || (ply == 0 && self.root_excluded.contains(&mv)) {
+				continue;
+			}
+
+			//Singular Extensions
+			//If every other move fails low against a margin below the TT score, the TT move is the only good move here and is searched deeper
+			//IF NOT root node
+			//IF the move is the TT move
+			//IF sufficient depth
+			//IF the TT entry is a lower bound or exact from a close enough depth and NOT a mate score
+			let mut extension = 0;
+			if let Some(table_find) = tt_hit.as_ref().filter(|_| ply > 0 && is_tt_move && !is_singular_search && depth >= se_depth_min()) {
+				if table_find.best_move == Some(mv)
+				&& table_find.node_kind != NodeKind::UpperBound
+				&& table_find.depth >= depth - se_tt_depth_margin()
+				&& table_find.eval.abs() < Score::MATE_THRESHOLD {
+					let singular_beta = table_find.eval - se_beta_margin() * depth;
+					let (_, singular_eval) = self.search(abort, boardwrapper, (depth - 1) / 2, ply, singular_beta - 1, singular_beta, past_positions, last_move, Some(mv))?;
+					self.clear_pv(ply);
+
+					if singular_eval.score < singular_beta {
+						extension = 1;
+
+						//Double Extension
+						//the other moves fall far short, capped per line so the search can not explode
+						if !is_pv && singular_eval.score < singular_beta - se_double_margin() && line_double_extensions < Self::DOUBLE_EXTENSION_CAP {
+							extension = 2;
+						}
+					} else if singular_beta >= beta {
+						//Multi-Cut
+						//the TT move is not the only move failing high, so this node is very likely to fail high too
+						return Some((None, Eval::new(singular_beta, false)));
+					} else if table_find.eval >= beta {
+						//Negative Extension
+						//another move can likely fail high too, spend less time on the TT move
+						extension = -1;
+					}
+				}
+			}
+
+			self.double_extensions[ply as usize] = line_double_extensions + (extension == 2) as i32;
+			self.move_stack[ply as usize] = Some(PieceTo::new(&boardwrapper.board, mv));
+
 			let mut board_wrapper_cache = boardwrapper.clone();
 				
-			board_wrapper_cache.play_unchecked(sm);
+			board_wrapper_cache.play_unchecked(&mut sm);
 
 			let move_is_check = !board_wrapper_cache.board.checkers().is_empty();
 
 			past_positions.push(board_wrapper_cache.board.hash());
 
+			let nodes_before = self.nodes;
 			let mut value: Eval;
-			let mut new_depth = depth - 1;
+			let mut new_depth = depth - 1 + extension;
 
 			//Extensions
 
 			//King Pawn Endgame Extension
 			let non_pawns = boardwrapper.board.pieces(Piece::Rook) | boardwrapper.board.pieces(Piece::Bishop) | boardwrapper.board.pieces(Piece::Queen) | boardwrapper.board.pieces(Piece::Knight);
-			if !(boardwrapper.board.occupied() & non_pawns).is_empty() && (board_wrapper_cache.board.occupied() & non_pawns).is_empty() && !globally_extended && !staged_movegen {
+			if !(boardwrapper.board.occupied() & non_pawns).is_empty() && (board_wrapper_cache.board.occupied() & non_pawns).is_empty() && !globally_extended && !is_tt_move {
 				new_depth += 1;
 			}
 
 			if moves_searched == 0 {
-				let (_, mut child_eval) = self.search(&abort, &board_wrapper_cache, new_depth, ply + 1, -beta, -alpha, past_positions, Some(mv))?;
+				let (_, mut child_eval) = self.search(abort, &board_wrapper_cache, new_depth, ply + 1, -beta, -alpha, past_positions, Some(mv), None)?;
 				child_eval.score *= -1;
 
 				value = child_eval;
@@ -442,19 +757,65 @@ impl Searcher<'_> {
 				//IF alpha is NOT a losing mate
 				//IF IS late move
 				//IF is NOT a check
-				if !is_pv && depth <= Self::LMP_DEPTH_MAX 
+				if !is_pv && depth <= lmp_depth_max() 
 				&& sm.movetype == MoveType::Quiet 
 				&& alpha > -Score::CHECKMATE_BASE 
-				&& moves_searched > ((mvlen / 6) * depth) - (!improving as i32 * 3)
+				&& moves_searched > ((mvlen / lmp_move_divisor()) * depth) - (!improving as i32 * lmp_improving())
 				&& !in_check {
+					self.stats.record(Stat::Lmp);
 					past_positions.pop();
 					break;
 				}
 
+				//Futility Pruning
+				//Quiet moves can not raise the static eval enough to reach alpha near the leaves
+				//IF isn't PV
+				//IF NOT in check
+				//IF low depth
+				//IF move is quiet and NOT a promotion
+				//IF move does NOT give check
+				//IF alpha is NOT a mate score
+				if !is_pv && !in_check && depth <= fp_depth_max()
+				&& sm.movetype == MoveType::Quiet
+				&& mv.promotion.is_none()
+				&& !move_is_check
+				&& alpha.abs() < Score::MATE_THRESHOLD
+				&& static_eval + fp_base() + fp_multiplier() * depth <= alpha {
+					self.stats.record(Stat::Futility);
+					past_positions.pop();
+					continue;
+				}
+
+				//SEE Pruning
+				//Skip moves that lose too much material in the exchange on their destination, allowing more loss at higher depth
+				//IF isn't PV
+				//IF NOT in check
+				//IF low depth
+				if !is_pv && !in_check && depth <= see_prune_depth_max() {
+					let threshold = if sm.movetype == MoveType::Quiet {
+						-see_quiet_margin() * depth
+					} else {
+						-see_noisy_margin() * depth * depth
+					};
+
+					if !self.movegen.sorter.see_ge(&boardwrapper.board, mv, threshold) {
+						self.stats.record(Stat::SEEPruning);
+						past_positions.pop();
+						continue;
+					}
+				}
+
 				//History Pruning
-				if depth >= Self::HISTORY_DEPTH_MIN && sm.history < -500 * depth {
+				//Skip quiet moves whose history shows they rarely work out
+				//IF isn't PV
+				//IF NOT in check
+				//IF move is quiet and NOT a promotion
+				if !is_pv && !in_check
+				&& sm.movetype == MoveType::Quiet
+				&& mv.promotion.is_none()
+				&& depth >= history_prune_depth_min() && sm.history < -history_prune_margin() * depth {
+					self.stats.record(Stat::HistoryPruning);
 					past_positions.pop();
-					legal_index += 1;
 					continue;
 				}
 
@@ -469,7 +830,7 @@ impl Searcher<'_> {
 				//IF the first X searched are searched
 				if moves_searched >= 2 
 				&& (!is_pv || sm.movetype == MoveType::Quiet || !move_is_check) {
-					reduction += self.get_lmr_reduction_amount(depth, moves_searched);
+					reduction += self.get_lmr_reduction_amount(is_pv, sm.movetype == MoveType::Quiet, depth, moves_searched);
 				}
 
 				//Reduce less if PV node
@@ -518,7 +879,11 @@ impl Searcher<'_> {
 					reduction = 0;
 				}
 
-				let (_, mut child_eval) = self.search(&abort, &board_wrapper_cache, new_depth - reduction, ply + 1, -alpha - 1, -alpha, past_positions, Some(mv))?;
+				if reduction > 0 {
+					self.stats.record(Stat::LMRSearches);
+				}
+
+				let (_, mut child_eval) = self.search(abort, &board_wrapper_cache, new_depth - reduction, ply + 1, -alpha - 1, -alpha, past_positions, Some(mv), None)?;
 				child_eval.score *= -1;
 
 				value = child_eval;
@@ -526,7 +891,8 @@ impl Searcher<'_> {
 				//check if reductions should be removed
 				//search with full depth and null window
 				if value.score > alpha && reduction > 0 {
-					let (_, mut child_eval) = self.search(&abort, &board_wrapper_cache, new_depth, ply + 1, -alpha - 1, -alpha, past_positions, Some(mv))?;
+					self.stats.record(Stat::LMRResearches);
+					let (_, mut child_eval) = self.search(abort, &board_wrapper_cache, new_depth, ply + 1, -alpha - 1, -alpha, past_positions, Some(mv), None)?;
 					child_eval.score *= -1;
 
 					value = child_eval;	
@@ -535,7 +901,7 @@ impl Searcher<'_> {
 				//if PV
 				//search with full depth and full window
 				if value.score > alpha && value.score < beta {
-					let (_, mut child_eval) = self.search(&abort, &board_wrapper_cache, new_depth, ply + 1, -beta, -alpha, past_positions, Some(mv))?;
+					let (_, mut child_eval) = self.search(abort, &board_wrapper_cache, new_depth, ply + 1, -beta, -alpha, past_positions, Some(mv), None)?;
 					child_eval.score *= -1;		
 
 					value = child_eval;	
@@ -544,6 +910,10 @@ impl Searcher<'_> {
 
 			past_positions.pop();
 
+			if ply == 0 {
+				self.root_nodes[mv.from as usize][mv.to as usize] += self.nodes - nodes_before;
+			}
+
 			let mut do_spp = false;
 
 			if value.score > eval.score {
@@ -552,10 +922,16 @@ impl Searcher<'_> {
 				best_move_type = Some(sm.movetype.clone());
 				if eval.score > alpha {
 					alpha = eval.score;
+					self.update_pv(ply, mv);
 					if alpha >= beta {
+						self.stats.record(Stat::BetaCutoffs);
+						if moves_searched == 0 {
+							self.stats.record(Stat::FirstMoveCutoffs);
+						}
+
 						tt_nodetype = NodeKind::LowerBound;
 						sm.insert_killer(&mut self.movegen.sorter, ply, &boardwrapper.board);
-						sm.insert_history(&mut self.movegen.sorter, depth);
+						sm.insert_history(&mut self.movegen.sorter, &boardwrapper.board, &conts, depth);
 						sm.insert_countermove(&mut self.movegen.sorter, last_move);
 						break;
 					} else {
@@ -564,32 +940,43 @@ impl Searcher<'_> {
 				} else {
 					//SPP
 					do_spp = !is_pv 
-					&& depth <= Self::SPP_DEPTH_CAP 
+					&& depth <= spp_depth_cap() 
 					&& !move_is_check 
 					&& !sm.is_killer
 					&& !sm.is_countermove
 					&& sm.movetype == MoveType::Quiet
-					&& !staged_movegen;
+					&& !is_tt_move;
 				}
 			}
 
-			sm.decay_history(&mut self.movegen.sorter, depth);
+			sm.decay_history(&mut self.movegen.sorter, &boardwrapper.board, &conts, depth);
 
 			if do_spp {
+				self.stats.record(Stat::Spp);
 				break;
 			}
 
 			moves_searched += 1;
-			legal_index += 1;
+		}
 
-			if staged_movegen && legal_index >= legal_moves.len() {
-				staged_movegen = false;
-				legal_index = 0; 
-				legal_moves = self.movegen.move_gen(&boardwrapper.board, Some(mv), ply, true, last_move);
+		self.return_move_buffer(ply, picker.into_buffer());
+
+		//with the singular move excluded the result does not describe the full position
+		if is_singular_search {
+			//every other move may have been pruned, which counts as failing low
+			if best_move.is_none() {
+				return Some((None, Eval::new(alpha, false)));
 			}
+
+			return Some((best_move, eval));
+		}
+
+		//later MultiPV lines leave out the best moves, so they must not replace the root entry or teach the correction histories
+		if ply == 0 && !self.root_excluded.is_empty() {
+			return Some((best_move, eval));
 		}
 
-		self.shared_info.tt.insert(best_move, eval.score, boardwrapper.board.hash(), ply, depth, tt_nodetype);
+		self.tt.insert(best_move, eval.score, raw_eval, boardwrapper.board.hash(), ply, depth, tt_nodetype);
 
 		if best_move_type.unwrap() == MoveType::Quiet
 		&& ( (tt_nodetype == NodeKind::UpperBound && eval.score < static_eval) || (tt_nodetype == NodeKind::LowerBound && eval.score > static_eval) ) {
@@ -601,13 +988,16 @@ impl Searcher<'_> {
 		return Some((best_move, eval));
 	}
 
-	fn qsearch(&mut self, abort: &AtomicBool, boardwrapper: &BoardWrapper, mut alpha: i32, beta: i32, mut ply: i32) -> Option<(Option<Move>, Eval)> {
+	//quiet checks are only searched when checks is set, which is the first ply of qsearch
+	fn qsearch(&mut self, abort: &AtomicBool, boardwrapper: &BoardWrapper, mut alpha: i32, beta: i32, mut ply: i32, checks: bool) -> Option<(Option<Move>, Eval)> {
 		//abort?
-		if self.time_control.depth > 1 && abort.load(Ordering::Relaxed) {
+		if self.time_control.depth > 1 && self.should_abort(abort) {
 			return None;
 		}
 
 		self.nodes += 1;
+		self.stats.record(Stat::QNodes);
+		self.clear_pv(ply);
 
 		match boardwrapper.board.status() {
 			GameStatus::Won => return Some((None, Eval::new(-Score::CHECKMATE_BASE + ply, true))),
@@ -615,27 +1005,24 @@ impl Searcher<'_> {
 			GameStatus::Ongoing => {}
 		}
 
-		let base_eval = evaluate(&boardwrapper.board) as f32;
-		let pawn_corrhist = self.movegen.sorter.read_pawn_corrhist(boardwrapper);
-		let non_pawn_corrhist = self.movegen.sorter.read_non_pawn_corrhist(boardwrapper);
-		let material_corrhist = self.movegen.sorter.read_material_corrhist(boardwrapper);
-		let stand_pat = Eval::new((
-			base_eval + pawn_corrhist + non_pawn_corrhist + material_corrhist
-		) as i32, false);
-
-		//beta cutoff
-		if stand_pat.score >= beta {
-			return Some((None, Eval::new(beta, false)));
-		}
+		let in_check = !boardwrapper.board.checkers().is_empty();
+		let raw_eval = boardwrapper.evaluate();
+		let stand_pat = Eval::new(self.static_eval(boardwrapper, raw_eval), false);
+
+		//in check there is no standing pat, every evasion is searched and failing to find a good one scores as mated
+		if !in_check {
+			//beta cutoff
+			if stand_pat.score >= beta {
+				return Some((None, Eval::new(beta, false)));
+			}
 
-		if alpha < stand_pat.score {
-			alpha = stand_pat.score;
+			if alpha < stand_pat.score {
+				alpha = stand_pat.score;
+			}
 		}
 
-		let mut move_list: Vec<SortedMove>;
-
 		//probe TT
-		let table_find = match self.shared_info.tt.find(&boardwrapper.board, ply) {
+		let table_find = match self.tt.find(&boardwrapper.board, ply) {
 			Some(table_find) => {
 				//check if position from TT is a mate
 				let mut is_checkmate = if table_find.eval < -Score::CHECKMATE_BASE || table_find.eval > Score::CHECKMATE_BASE {
@@ -661,38 +1048,39 @@ impl Searcher<'_> {
 					NodeKind::Null => {}
 				}
 
-				move_list = self.movegen.qmove_gen(&boardwrapper.board, table_find.best_move, ply);
-
 				Some(table_find)
 			},
-			None => {
-				move_list = self.movegen.qmove_gen(&boardwrapper.board, None, ply);
-
-				None
-			}
+			None => None
 		};
 
-		//no more loud moves to be checked anymore, it can be returned safely
-		if move_list.len() == 0 {
-			return Some((None, stand_pat));
-		}
+		let tt_move = table_find.and_then(|table_find| table_find.best_move);
+		let mut picker = MovePicker::new_quiescence(self.take_move_buffer(ply), &boardwrapper.board, tt_move, ply, checks);
 
 		let mut best_move = None;
-		let mut eval = stand_pat;
+		let mut eval = if in_check { Eval::new(-Score::CHECKMATE_BASE + ply, true) } else { stand_pat.clone() };
 		let mut tt_nodetype = NodeKind::UpperBound;
 
-		for mut sm in move_list {
+		//losing captures found through SEE swap algorithm are pruned by the picker
+		while let Some(mut sm) = picker.next(&mut self.movegen.sorter, &boardwrapper.board) {
+			let mv = sm.mv;
 
-			//prune losing captures found through SEE swap algorithm
-			if sm.importance < 0 {
-				break;
+			//Delta Pruning
+			//skip captures that can not raise alpha even when winning the captured piece outright
+			//IF NOT in check
+			//IF NOT a promotion
+			//IF alpha is NOT a mate score
+			if !in_check && mv.promotion.is_none() && alpha.abs() < Score::MATE_THRESHOLD {
+				if let Some(captured) = boardwrapper.board.piece_on(mv.to).filter(|_| sm.movetype == MoveType::Loud) {
+					if stand_pat.score + self.movegen.sorter.piece_value(captured) + delta_margin() <= alpha {
+						continue;
+					}
+				}
 			}
 
-			let mv = sm.mv;
 			let mut board_wrapper_cache = boardwrapper.clone();
 			board_wrapper_cache.play_unchecked(&mut sm);
 
-			let (_, mut child_eval) = self.qsearch(&abort, &board_wrapper_cache, -beta, -alpha, ply + 1)?;
+			let (_, mut child_eval) = self.qsearch(abort, &board_wrapper_cache, -beta, -alpha, ply + 1, false)?;
 
 			child_eval.score *= -1;
 
@@ -702,6 +1090,7 @@ impl Searcher<'_> {
 				best_move = Some(mv);
 				if eval.score > alpha {
 					alpha = eval.score;
+					self.update_pv(ply, mv);
 					if alpha >= beta {
 						tt_nodetype = NodeKind::LowerBound;
 						break;
@@ -714,9 +1103,10 @@ impl Searcher<'_> {
 			}
 		}
 
+		self.return_move_buffer(ply, picker.into_buffer());
 
 		if best_move.is_some() {
-			self.shared_info.tt.insert(best_move, eval.score, boardwrapper.board.hash(), ply, 0, tt_nodetype);
+			self.tt.insert(best_move, eval.score, raw_eval, boardwrapper.board.hash(), ply, 0, tt_nodetype);
 		}
 
 		return Some((best_move, eval));
@@ -724,11 +1114,9 @@ impl Searcher<'_> {
 }
 
 impl Searcher<'_> {
-	const MAX_DEPTH_RFP: i32 = 6;
-	const MULTIPLIER_RFP: i32 = 80;
-	const HISTORY_DEPTH_MIN: i32 = 5;
-	const IID_DEPTH_MIN: i32 = 6;
-	const LMP_DEPTH_MAX: i32 = 3;
-	const SPP_DEPTH_CAP: i32 = 3;
 	const UNDERPROMO_REDUC_DEPTH: i32 = 4;
+	const DOUBLE_EXTENSION_CAP: i32 = 6;
+	const HELPER_WINDOW_STEP: i32 = 5;
+	const SKIP_SIZE: [i32; 20] = [1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 4, 4, 4, 4, 4, 4, 4, 4];
+	const SKIP_PHASE: [i32; 20] = [0, 1, 0, 1, 2, 3, 0, 1, 2, 3, 4, 5, 0, 1, 2, 3, 4, 5, 6, 7];
 }
\ No newline at end of file
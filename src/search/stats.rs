@@ -0,0 +1,111 @@
+/*
+Search statistics for tuning pruning, only collected when built with the stats feature
+cargo build --release --features stats
+Without the feature the counters do not exist and recording compiles to nothing
+*/
+
+#[derive(Clone, Copy)]
+pub enum Stat {
+	Nodes,
+	QNodes,
+	TTProbes,
+	TTHits,
+	TTCutoffs,
+	BetaCutoffs,
+	FirstMoveCutoffs,
+	NMPTries,
+	NMPCutoffs,
+	Rfp,
+	Razoring,
+	ProbCut,
+	Lmp,
+	Futility,
+	SEEPruning,
+	HistoryPruning,
+	Spp,
+	LMRSearches,
+	LMRResearches
+}
+
+#[derive(Clone, Copy)]
+pub struct SearchStats {
+	#[cfg(feature = "stats")]
+	counters: [u64; Stat::COUNT]
+}
+
+impl SearchStats {
+	pub const ENABLED: bool = cfg!(feature = "stats");
+
+	pub fn new() -> SearchStats {
+		SearchStats {
+			#[cfg(feature = "stats")]
+			counters: [0; Stat::COUNT]
+		}
+	}
+
+	#[inline(always)]
+	pub fn record(&mut self, stat: Stat) {
+		#[cfg(feature = "stats")]
+		{
+			self.counters[stat as usize] += 1;
+		}
+
+		#[cfg(not(feature = "stats"))]
+		let _ = stat;
+	}
+
+	pub fn merge(&mut self, other: &SearchStats) {
+		#[cfg(feature = "stats")]
+		for i in 0..Stat::COUNT {
+			self.counters[i] += other.counters[i];
+		}
+
+		#[cfg(not(feature = "stats"))]
+		let _ = other;
+	}
+
+	#[cfg(feature = "stats")]
+	fn get(&self, stat: Stat) -> u64 {
+		self.counters[stat as usize]
+	}
+
+	#[cfg(feature = "stats")]
+	fn percent(part: u64, whole: u64) -> f64 {
+		part as f64 * 100.0 / whole.max(1) as f64
+	}
+
+	//one line for an info string, rates are in percent
+	#[cfg(feature = "stats")]
+	pub fn report(&self) -> String {
+		let nodes = self.get(Stat::Nodes) + self.get(Stat::QNodes);
+
+		format!(
+			"tt hits {:.1}% tt cutoffs {:.1}% first move cutoffs {:.1}% nmp {}/{} rfp {} razoring {} probcut {} lmp {} fp {} see {} hp {} spp {} lmr researches {:.1}% qnodes {:.1}%",
+			Self::percent(self.get(Stat::TTHits), self.get(Stat::TTProbes)),
+			Self::percent(self.get(Stat::TTCutoffs), self.get(Stat::TTProbes)),
+			Self::percent(self.get(Stat::FirstMoveCutoffs), self.get(Stat::BetaCutoffs)),
+			self.get(Stat::NMPCutoffs),
+			self.get(Stat::NMPTries),
+			self.get(Stat::Rfp),
+			self.get(Stat::Razoring),
+			self.get(Stat::ProbCut),
+			self.get(Stat::Lmp),
+			self.get(Stat::Futility),
+			self.get(Stat::SEEPruning),
+			self.get(Stat::HistoryPruning),
+			self.get(Stat::Spp),
+			Self::percent(self.get(Stat::LMRResearches), self.get(Stat::LMRSearches)),
+			Self::percent(self.get(Stat::QNodes), nodes)
+		)
+	}
+
+	#[cfg(not(feature = "stats"))]
+	pub fn report(&self) -> String {
+		String::new()
+	}
+}
+
+impl Stat {
+	#[cfg(feature = "stats")]
+	const COUNT: usize = Stat::LMRResearches as usize + 1;
+}
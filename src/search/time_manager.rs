@@ -0,0 +1,197 @@
+use cozy_chess::*;
+
+use crate::search::search_master::*;
+use crate::search::clock::*;
+
+/*
+Decides how long a search may run
+The optimum time is a soft limit checked between iterations, scaled by how settled the search looks:
+a best move that keeps changing, a dropping score or a best move that took few of the nodes all buy more time
+The maximum time is a hard limit the main thread checks inside the search every TIME_CHECK_INTERVAL nodes
+Both are taken from the clock left after the move overhead, with movestogo spreading it over the rest of a repeating control
+*/
+
+pub struct TimeManager {
+	clock: Box<dyn Clock>,
+	optimum: Option<u64>,
+	maximum: Option<u64>,
+	last_best_move: Option<Move>,
+	last_score: Option<i32>,
+	stability: usize
+}
+
+impl TimeManager {
+	pub fn new(time_control: &TimeControl, board: &Board) -> TimeManager {
+		let (time, inc) = match board.side_to_move() {
+			Color::White => (time_control.wtime, time_control.winc),
+			Color::Black => (time_control.btime, time_control.binc)
+		};
+
+		//lag between us and the GUI is paid on every move, so it never counts as thinking time
+		let overhead = time_control.move_overhead.max(0);
+
+		//a fixed movetime is used in full
+		let (mut optimum, maximum) = if let Some(movetime) = time_control.movetime {
+			(None, Some((movetime - overhead).max(0) as u64))
+		} else if time != i64::MAX {
+			let time = (time - overhead).max(0) as u64;
+			let inc = inc.max(0) as u64;
+
+			let (optimum, maximum) = if time < Self::EMERGENCY_TIME {
+				//nearly flagging, move fast and live off the increment
+				(time / Self::EMERGENCY_SOFT_DIVISOR + inc / 2, time / Self::EMERGENCY_HARD_DIVISOR + inc / 2)
+			} else if let Some(movestogo) = time_control.movestogo {
+				//repeating controls, only part of the per move share is used so the leftover builds up
+				//and is spent as the time control approaches
+				let movestogo = movestogo.clamp(1, Self::MAX_MOVES_TO_GO) as u64;
+				let time_left = time + inc * (movestogo - 1);
+
+				let optimum = time_left / movestogo * Self::CYCLIC_SOFT_PERCENT / 100;
+
+				//never more than a couple of shares on one move, and always leave something for the rest of the control
+				//the reserve shrinks with the clock so a short one still leaves time for this move
+				let mut maximum = (optimum * Self::CYCLIC_HARD_MULTIPLIER).min(time_left / movestogo * Self::CYCLIC_MAX_SHARES);
+				if movestogo > 1 {
+					let reserve = ((movestogo - 1) * Self::MIN_TIME_PER_MOVE).min(time / 2);
+					maximum = maximum.min(time - reserve);
+				}
+
+				(optimum, maximum)
+			} else {
+				((time + inc) / Self::SOFT_DIVISOR, (time + inc) / Self::HARD_DIVISOR)
+			};
+
+			let maximum = maximum.min(time * Self::MAX_TIME_PERCENT / 100);
+			(Some(optimum.min(maximum)), Some(maximum))
+		} else {
+			(None, None)
+		};
+
+		//with a single legal move there is nothing to think about
+		let mut legal_moves = 0;
+		board.generate_moves(|moves| {
+			legal_moves += moves.len();
+			false
+		});
+
+		if legal_moves == 1 && maximum.is_some() {
+			optimum = Some(0);
+		}
+
+		TimeManager {
+			clock: match time_control.simulated_nps {
+				Some(nps) => Box::new(SimulatedClock::new(nps)),
+				None => Box::new(WallClock::new())
+			},
+			optimum,
+			maximum,
+			last_best_move: None,
+			last_score: None,
+			stability: 0
+		}
+	}
+
+	pub fn elapsed(&self, nodes: u64) -> u64 {
+		self.clock.elapsed(nodes)
+	}
+
+	pub fn hard_stop(&self, nodes: u64) -> bool {
+		self.maximum.is_some_and(|maximum| self.elapsed(nodes) >= maximum)
+	}
+
+	//called by the main thread after every finished iteration
+	pub fn soft_stop(&mut self, best_move: Option<Move>, score: i32, best_move_nodes: u64, total_nodes: u64) -> bool {
+		if best_move == self.last_best_move {
+			self.stability = (self.stability + 1).min(Self::STABILITY_SCALE.len() - 1);
+		} else {
+			self.stability = 0;
+		}
+
+		let score_drop = self.last_score.map_or(0, |last_score| last_score - score);
+
+		self.last_best_move = best_move;
+		self.last_score = Some(score);
+
+		let optimum = match self.optimum {
+			Some(optimum) => optimum as f32,
+			None => return false
+		};
+
+		let stability_scale = Self::STABILITY_SCALE[self.stability];
+		let score_scale = (1.0 + score_drop as f32 / Self::SCORE_DROP_DIVISOR).clamp(Self::SCORE_SCALE_MIN, Self::SCORE_SCALE_MAX);
+		let node_fraction = best_move_nodes as f32 / total_nodes.max(1) as f32;
+		let node_scale = (Self::NODE_SCALE_BASE - node_fraction) * Self::NODE_SCALE_MULTIPLIER;
+
+		let soft_limit = (optimum * stability_scale * score_scale * node_scale) as u64;
+		self.elapsed(total_nodes) >= soft_limit.min(self.maximum.unwrap_or(u64::MAX))
+	}
+}
+
+impl TimeManager {
+	pub const TIME_CHECK_INTERVAL: u64 = 1024;
+
+	const SOFT_DIVISOR: u64 = 25;
+	const HARD_DIVISOR: u64 = 2;
+	const MAX_TIME_PERCENT: u64 = 75;
+
+	const MAX_MOVES_TO_GO: i64 = 50;
+	const CYCLIC_SOFT_PERCENT: u64 = 70;
+	const CYCLIC_HARD_MULTIPLIER: u64 = 3;
+	const CYCLIC_MAX_SHARES: u64 = 2;
+	//kept back for every other move left in a repeating control
+	const MIN_TIME_PER_MOVE: u64 = 100;
+
+	//below this many milliseconds the clock is nearly empty
+	const EMERGENCY_TIME: u64 = 1000;
+	const EMERGENCY_SOFT_DIVISOR: u64 = 40;
+	const EMERGENCY_HARD_DIVISOR: u64 = 10;
+
+	//indexed by how many iterations in a row returned the same best move
+	const STABILITY_SCALE: [f32; 5] = [2.2, 1.6, 1.2, 1.0, 0.85];
+
+	const SCORE_DROP_DIVISOR: f32 = 100.0;
+	const SCORE_SCALE_MIN: f32 = 1.0;
+	const SCORE_SCALE_MAX: f32 = 1.8;
+
+	const NODE_SCALE_BASE: f32 = 1.5;
+	const NODE_SCALE_MULTIPLIER: f32 = 1.35;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn time_manager(wtime: i64, movestogo: i64) -> TimeManager {
+		let mut time_control = TimeControl::new();
+		time_control.wtime = wtime;
+		time_control.btime = wtime;
+		time_control.movestogo = Some(movestogo);
+		time_control.move_overhead = 10;
+		time_control.simulated_nps = Some(1000000);
+
+		TimeManager::new(&time_control, &Board::default())
+	}
+
+	//a short clock with many moves to go must still leave this move about its share
+	#[test]
+	fn short_repeating_control_keeps_a_share() {
+		let time_manager = time_manager(3000, 40);
+		let optimum = time_manager.optimum.unwrap();
+		let maximum = time_manager.maximum.unwrap();
+
+		assert!(optimum > 0);
+		assert!(maximum >= optimum && maximum >= 2990 / 40);
+		assert!(!time_manager.hard_stop(0));
+		assert!(time_manager.hard_stop(maximum * 1000));
+	}
+
+	//a few moves to go never spend the clock needed for the rest of them
+	#[test]
+	fn repeating_control_keeps_a_reserve() {
+		let time_manager = time_manager(3000, 3);
+		let maximum = time_manager.maximum.unwrap();
+
+		assert!(maximum <= 2990 / 3 * TimeManager::CYCLIC_MAX_SHARES);
+		assert!(maximum <= 2990 - 2 * TimeManager::MIN_TIME_PER_MOVE);
+	}
+}
\ No newline at end of file
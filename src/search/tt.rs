@@ -22,6 +22,7 @@ pub enum NodeKind {
 pub struct TTEntry {
 	pub best_move: Option<Move>,
 	pub eval: i32,
+	pub static_eval: i32,
 	pub depth: i32,
 	pub node_kind: NodeKind
 }
@@ -32,13 +33,24 @@ pub struct TTSlot {
 	data: AtomicU64
 }
 
+//one cache line worth of slots
+#[derive(Debug)]
+#[repr(C, align(64))]
+pub struct TTBucket {
+	slots: [TTSlot; TT::BUCKET_SIZE]
+}
+
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
 #[repr(C)]
 struct EncodedEntry {
-	eval: i32,
+	eval: i16,
+	//raw evaluation before any corrections
+	static_eval: i16,
+	//0 means there is no move, which no real move encodes to since from and to always differ
 	mv_byte: u16,
 	depth: u8,
-	node_kind: u8
+	//node kind in the low 2 bits, search generation in the rest
+	kind_age: u8
 }
 
 //adjust tricky mate scores to make valid eval
@@ -70,23 +82,72 @@ impl TTSlot {
 		}
 	}
 
-	fn store(&self, best_move: Option<Move>, eval: i32, position: u64, depth: i32, node_kind: NodeKind) {	
+	#[allow(clippy::too_many_arguments)]
+	fn store(&self, best_move: Option<Move>, eval: i32, static_eval: i32, position: u64, depth: i32, node_kind: NodeKind, generation: u8) {	
 		let mut move_bits = 0u16;
-		move_bits = (move_bits << 6) | best_move.unwrap().from as u16;
-		move_bits = (move_bits << 6) | best_move.unwrap().to as u16;
-		move_bits = (move_bits << 4) | best_move.unwrap().promotion.map_or(0b1111, |p| p as u16);
+		if let Some(best_move) = best_move {
+			move_bits = (move_bits << 6) | best_move.from as u16;
+			move_bits = (move_bits << 6) | best_move.to as u16;
+			move_bits = (move_bits << 4) | best_move.promotion.map_or(0b1111, |p| p as u16);
+		}
 
 		let data = bytemuck::cast(EncodedEntry {
-			eval: eval,
+			eval: eval.clamp(-i16::MAX as i32, i16::MAX as i32) as i16,
+			static_eval: static_eval.clamp(-i16::MAX as i32, i16::MAX as i32) as i16,
 			mv_byte: move_bits,
-			depth: depth as u8,
-			node_kind: node_kind as u8
+			//a negative depth would wrap around and pass for the deepest entry in the table
+			depth: depth.clamp(0, u8::MAX as i32) as u8,
+			kind_age: node_kind as u8 | (generation << 2)
 		});
 
 		self.position_hash.store(position ^ data, Ordering::Relaxed);
 		self.data.store(data, Ordering::Relaxed);
 	}
 
+	fn matches(&self, position: u64) -> bool {
+		let data = self.data.load(Ordering::Relaxed);
+		data != 0 && self.position_hash.load(Ordering::Relaxed) ^ data == position
+	}
+
+	fn is_empty(&self) -> bool {
+		self.data.load(Ordering::Relaxed) == 0
+	}
+
+	fn depth(&self) -> i32 {
+		let data: EncodedEntry = bytemuck::cast(self.data.load(Ordering::Relaxed));
+		data.depth as i32
+	}
+
+	//how many searches ago this slot was written
+	fn age(&self, generation: u8) -> i32 {
+		let data: EncodedEntry = bytemuck::cast(self.data.load(Ordering::Relaxed));
+		(generation.wrapping_sub(data.kind_age >> 2) & TT::GENERATION_MASK) as i32
+	}
+
+	fn best_move(&self) -> Option<Move> {
+		let data: EncodedEntry = bytemuck::cast(self.data.load(Ordering::Relaxed));
+		Self::decode_move(data.mv_byte)
+	}
+
+	fn decode_move(mv_byte: u16) -> Option<Move> {
+		if mv_byte == 0 {
+			return None;
+		}
+
+		let mut move_bits = mv_byte;
+		let promotion = move_bits & 0b1111;
+		move_bits >>= 4;
+		let to = move_bits & 0b111111;
+		move_bits >>= 6;
+		let from = move_bits & 0b111111;
+
+		Some(Move {
+			from: Square::index(from as usize),
+			to: Square::index(to as usize),
+			promotion: Piece::try_index(promotion as usize)
+		})
+	}
+
 	fn load(&self, board: &Board, ply: i32) -> Option<TTEntry> {
 		let position_hash_data = self.position_hash.load(Ordering::Relaxed);
 		let data = self.data.load(Ordering::Relaxed);
@@ -97,23 +158,12 @@ impl TTSlot {
 		} else {
 			let data: EncodedEntry = bytemuck::cast(data);
 
-			let mut move_bits = data.mv_byte;
-			let promotion = move_bits & 0b1111;
-			move_bits >>= 4;
-			let to = move_bits & 0b111111;
-			move_bits >>= 6;
-			let from = move_bits & 0b111111;
-			move_bits >>= 6;
-
 			Some(TTEntry {
-				best_move: Some(Move {
-					from: Square::index(from as usize),
-					to: Square::index(to as usize),
-					promotion: Piece::try_index(promotion as usize)
-				}),
-				eval: add_mate_score(data.eval, ply),
+				best_move: Self::decode_move(data.mv_byte),
+				eval: add_mate_score(data.eval as i32, ply),
+				static_eval: data.static_eval as i32,
 				depth: data.depth as i32,
-				node_kind: match data.node_kind {
+				node_kind: match data.kind_age & 0b11 {
 					0 => NodeKind::Exact,
 					1 => NodeKind::UpperBound,
 					2 => NodeKind::LowerBound,
@@ -124,26 +174,81 @@ impl TTSlot {
 	}
 }
 
+impl TTBucket {
+	fn empty() -> TTBucket {
+		TTBucket {
+			slots: [TTSlot::empty(), TTSlot::empty(), TTSlot::empty(), TTSlot::empty()]
+		}
+	}
+}
+
 pub struct TT {
-	pub table: Box<[TTSlot]>,
-	length: u64
+	pub table: Box<[TTBucket]>,
+	length: u64,
+	generation: u8
 }
 
 impl TT {
+	//a hash size of 0 gives a disabled table that never stores anything
 	pub fn new(hash: u32) -> TT {
-		let tt_length = (hash as usize * 1024 * 1024 / std::mem::size_of::<TTSlot>()) as u64;
+		let tt_length = (hash as usize * 1024 * 1024 / std::mem::size_of::<TTBucket>()) as u64;
 
 		TT {
-			table: (0..tt_length).map(|_| TTSlot::empty()).collect(),
-			length: tt_length
+			table: (0..tt_length).map(|_| TTBucket::empty()).collect(),
+			length: tt_length,
+			generation: 0
 		}
 	}
 
-	pub fn insert(&self, best_move: Option<Move>, eval: i32, position: u64, ply: i32, depth: i32, node_kind: NodeKind) {
-		self.table[(position % self.length) as usize].store(best_move, remove_mate_score(eval, ply), position, depth, node_kind);
+	//called once per search so entries from older searches are replaced first
+	pub fn new_search(&mut self) {
+		self.generation = (self.generation + 1) & Self::GENERATION_MASK;
+	}
+
+	//multiply-shift maps the hash onto the table without a division
+	fn bucket(&self, position: u64) -> Option<&TTBucket> {
+		if self.length == 0 {
+			return None;
+		}
+
+		Some(&self.table[((position as u128 * self.length as u128) >> 64) as usize])
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn insert(&self, best_move: Option<Move>, eval: i32, static_eval: i32, position: u64, ply: i32, depth: i32, node_kind: NodeKind) {
+		let bucket = match self.bucket(position) {
+			Some(bucket) => bucket,
+			None => return
+		};
+
+		//the same position already stored is only overwritten by an exact, deep enough or newer entry, and keeps its move if there is no new one
+		if let Some(slot) = bucket.slots.iter().find(|slot| slot.matches(position)) {
+			if node_kind == NodeKind::Exact || depth + Self::REPLACE_DEPTH_MARGIN > slot.depth() || slot.age(self.generation) > 0 {
+				let best_move = best_move.or_else(|| slot.best_move());
+				slot.store(best_move, remove_mate_score(eval, ply), static_eval, position, depth, node_kind, self.generation);
+			}
+
+			return;
+		}
+
+		//otherwise take an empty slot, or the shallowest and oldest one
+		let slot = match bucket.slots.iter().find(|slot| slot.is_empty()) {
+			Some(slot) => slot,
+			None => bucket.slots.iter().min_by_key(|slot| slot.depth() - slot.age(self.generation) * Self::AGE_WEIGHT).unwrap()
+		};
+
+		slot.store(best_move, remove_mate_score(eval, ply), static_eval, position, depth, node_kind, self.generation);
 	}
 
 	pub fn find(&self, board: &Board, ply: i32) -> Option<TTEntry> {
-		self.table[(board.hash() % self.length) as usize].load(board, ply)
+		let bucket = self.bucket(board.hash())?;
+		bucket.slots.iter().find_map(|slot| slot.load(board, ply))
 	}
-}
\ No newline at end of file
+}
+
+impl TT {
+	const BUCKET_SIZE: usize = 4;
+	const GENERATION_MASK: u8 = 0b111111;
+	const AGE_WEIGHT: i32 = 2;
+	const REPLACE_DEPTH_MARGIN: i32 = 4;
+}
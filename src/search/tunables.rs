@@ -0,0 +1,143 @@
+use std::sync::atomic::{AtomicI32, Ordering};
+
+/*
+Search parameters that can be changed at runtime for SPSA tuning
+Every tunable is printed as a UCI spin option, set with setoption and read through a getter of the same name
+The spsa command lists them in the OpenBench SPSA input format: name, int, default, min, max, c_end, r_end
+*/
+
+const R_END: f32 = 0.002;
+
+pub struct Tunable {
+	pub name: &'static str,
+	value: &'static AtomicI32,
+	pub default: i32,
+	pub min: i32,
+	pub max: i32,
+	pub c_end: f32
+}
+
+impl Tunable {
+	pub fn set(&self, value: i32) {
+		self.value.store(value, Ordering::Relaxed);
+	}
+}
+
+macro_rules! tunables {
+	($($name:ident: $default:expr, $min:expr, $max:expr, $c_end:expr;)*) => {
+		#[allow(non_upper_case_globals)]
+		mod values {
+			use std::sync::atomic::AtomicI32;
+
+			$(pub static $name: AtomicI32 = AtomicI32::new($default);)*
+		}
+
+		$(
+			#[inline(always)]
+			pub fn $name() -> i32 {
+				values::$name.load(Ordering::Relaxed)
+			}
+		)*
+
+		pub static TUNABLES: &[Tunable] = &[
+			$(Tunable {
+				name: stringify!($name),
+				value: &values::$name,
+				default: $default,
+				min: $min,
+				max: $max,
+				c_end: $c_end
+			},)*
+		];
+	};
+}
+
+tunables! {
+	//reverse futility pruning
+	rfp_depth_max: 6, 1, 12, 0.5;
+	rfp_multiplier: 80, 20, 200, 8.0;
+	rfp_improving_margin: 30, 0, 100, 5.0;
+
+	//razoring
+	razor_depth_max: 3, 1, 6, 0.5;
+	razor_margin: 250, 100, 500, 20.0;
+
+	//null move pruning, reduction is base + depth / depth_divisor + (eval - beta) / eval_divisor
+	nmp_base: 2, 1, 5, 0.5;
+	nmp_depth_divisor: 3, 1, 8, 0.5;
+	nmp_eval_divisor: 128, 32, 512, 16.0;
+
+	//probcut, captures are verified against beta + margin with a search reduced by reduction
+	probcut_depth_min: 5, 3, 10, 0.5;
+	probcut_margin: 200, 50, 400, 15.0;
+	probcut_reduction: 4, 2, 6, 0.5;
+
+	//late move pruning
+	lmp_depth_max: 3, 1, 8, 0.5;
+	lmp_move_divisor: 6, 2, 12, 0.5;
+	lmp_improving: 3, 0, 10, 0.5;
+
+	//late move reductions, base and divisor in hundredths
+	lmr_base_quiet: 75, 0, 200, 8.0;
+	lmr_divisor_quiet: 225, 100, 400, 12.0;
+	lmr_base_noisy: 75, 0, 200, 8.0;
+	lmr_divisor_noisy: 225, 100, 400, 12.0;
+	lmr_base_pv_quiet: 75, 0, 200, 8.0;
+	lmr_divisor_pv_quiet: 225, 100, 400, 12.0;
+	lmr_base_pv_noisy: 75, 0, 200, 8.0;
+	lmr_divisor_pv_noisy: 225, 100, 400, 12.0;
+
+	//singular extensions, the singular beta is the TT score minus beta_margin * depth
+	se_depth_min: 8, 4, 12, 0.5;
+	se_tt_depth_margin: 3, 1, 6, 0.5;
+	se_beta_margin: 2, 1, 8, 0.5;
+	se_double_margin: 20, 0, 100, 5.0;
+
+	//futility pruning, margin is base + multiplier * depth
+	fp_depth_max: 6, 1, 10, 0.5;
+	fp_base: 150, 0, 400, 15.0;
+	fp_multiplier: 100, 20, 250, 10.0;
+
+	//qsearch delta pruning
+	delta_margin: 200, 50, 500, 15.0;
+
+	//see pruning, quiets need SEE >= -quiet_margin * depth and captures SEE >= -noisy_margin * depth^2
+	see_prune_depth_max: 8, 2, 12, 0.5;
+	see_quiet_margin: 70, 20, 150, 6.0;
+	see_noisy_margin: 25, 5, 80, 3.0;
+
+	//history pruning
+	history_prune_depth_min: 5, 1, 12, 0.5;
+	history_prune_margin: 500, 100, 1500, 50.0;
+
+	//static pre-pruning and internal iterative deepening
+	spp_depth_cap: 3, 1, 8, 0.5;
+	iid_depth_min: 6, 2, 12, 0.5;
+
+	//aspiration windows
+	aspiration_window: 10, 5, 50, 2.0;
+
+	//correction histories
+	corrhist_weight_divisor: 596, 200, 1200, 30.0;
+	corrhist_update_scale: 280, 100, 600, 15.0;
+	corrhist_error_clamp: 81, 20, 200, 5.0;
+	pawn_corrhist_divisor: 198, 64, 512, 10.0;
+	non_pawn_corrhist_divisor: 202, 64, 512, 10.0;
+	material_corrhist_divisor: 289, 64, 768, 15.0;
+}
+
+pub fn find_tunable(name: &str) -> Option<&'static Tunable> {
+	TUNABLES.iter().find(|tunable| tunable.name == name)
+}
+
+pub fn print_uci_options() {
+	for tunable in TUNABLES {
+		println!("option name {} type spin default {} min {} max {}", tunable.name, tunable.default, tunable.min, tunable.max);
+	}
+}
+
+pub fn print_spsa_inputs() {
+	for tunable in TUNABLES {
+		println!("{}, int, {}, {}, {}, {}, {}", tunable.name, tunable.default, tunable.min, tunable.max, tunable.c_end, R_END);
+	}
+}
@@ -0,0 +1,56 @@
+use std::fmt::Write;
+
+/*
+Writes tuned weights back out in the layout of eval_info.rs
+*/
+
+const SECTIONS: &[(&str, &str)] = &[
+	("PAWN", "//PIECE WEIGHTS"),
+	("TEMPO", "//EXTRA CALCS"),
+	("P", "//PSTs")
+];
+
+const PER_LINE: usize = 8;
+const PST_LENGTH: usize = 64;
+
+fn score_str(weight: &[f64; 2]) -> String {
+	format!("S!({:4}, {:4})", weight[0].round() as i32, weight[1].round() as i32)
+}
+
+//terms are (const name, is array, [mg, eg] weights) in eval_info order
+pub fn generate_eval_info(terms: &[(&str, bool, Vec<[f64; 2]>)]) -> String {
+	let mut out = String::from("use crate::eval::score::*;\n");
+	let mut last_was_array = None;
+
+	for (name, is_array, weights) in terms {
+		if let Some((_, comment)) = SECTIONS.iter().find(|(first, _)| first == name) {
+			write!(out, " \n{}\n", comment);
+		} else if last_was_array.is_some() && last_was_array != Some(*is_array) {
+			out += "\n";
+		}
+
+		if *is_array {
+			writeln!(out, "pub const {}: [Score; {}] = [", name, weights.len());
+
+			let lines: Vec<String> = weights.chunks(PER_LINE).map(|line| {
+				line.iter().map(score_str).collect::<Vec<String>>().join(", ")
+			}).collect();
+
+			if weights.len() == PST_LENGTH {
+				writeln!(out, "    {}", lines.join(", \n    "));
+			} else {
+				for line in lines {
+					writeln!(out, "    {}, ", line);
+				}
+			}
+
+			out += "];\n";
+		} else {
+			writeln!(out, "pub const {}: Score = {};", name, score_str(&weights[0]));
+		}
+
+		last_was_array = Some(*is_array);
+	}
+
+	out
+}
@@ -0,0 +1,2 @@
+pub mod tuner;
+pub mod codegen;
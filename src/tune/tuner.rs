@@ -0,0 +1,325 @@
+use cozy_chess::*;
+
+use std::fs;
+use std::thread;
+use std::time::Instant;
+
+use crate::eval::score::*;
+use crate::eval::terms::*;
+use crate::eval::evaluator::*;
+use crate::tune::codegen::*;
+
+/*
+Texel tuner for the weights in eval_info.rs
+Usage: tune <dataset> [epochs N] [lr X] [threads N] [out PATH] [params PATH]
+Tuning starts from the loaded eval params, and params PATH also writes the result as a parameter file
+Dataset lines are either datagen output (FEN | score | result) or FEN followed by a result like [1.0], "1-0" or 1/2-1/2
+https://www.chessprogramming.org/Texel%27s_Tuning_Method
+*/
+
+const EPOCHS: usize = 1000;
+const LEARNING_RATE: f64 = 1.0;
+const OUT: &str = "eval_info.rs";
+const REPORT_INTERVAL: usize = 50;
+const SAVE_INTERVAL: usize = 100;
+
+const BETA1: f64 = 0.9;
+const BETA2: f64 = 0.999;
+const EPSILON: f64 = 1e-8;
+
+//a position reduced to its non zero trace coefficients
+struct Entry {
+	start: usize,
+	len: usize,
+	phase: f64,
+	scale: f64,
+	result: f64
+}
+
+#[derive(Default)]
+struct Shard {
+	entries: Vec<Entry>,
+	coefs: Vec<(u16, i16)>
+}
+
+impl Shard {
+	fn eval(&self, entry: &Entry, params: &[[f64; 2]]) -> f64 {
+		let mut mg = 0.0;
+		let mut eg = 0.0;
+
+		for &(index, coef) in &self.coefs[entry.start..entry.start + entry.len] {
+			mg += params[index as usize][0] * coef as f64;
+			eg += params[index as usize][1] * coef as f64;
+		}
+
+		let total_phase = Score::TOTAL_PHASE as f64;
+		(mg * (total_phase - entry.phase) + eg * entry.phase) / total_phase / entry.scale
+	}
+}
+
+fn sigmoid(k: f64, eval: f64) -> f64 {
+	1.0 / (1.0 + 10f64.powf(-k * eval / 400.0))
+}
+
+fn parse_result(result: &str) -> Option<f64> {
+	let result = result.trim().trim_matches(|c| c == '[' || c == ']' || c == '"' || c == ';').trim();
+
+	match result {
+		"1-0" => Some(1.0),
+		"0-1" => Some(0.0),
+		"1/2-1/2" => Some(0.5),
+		_ => result.parse::<f64>().ok()
+	}
+}
+
+fn parse_line(line: &str) -> Option<(Board, f64)> {
+	let (fen, result) = if line.contains('|') {
+		let fields: Vec<&str> = line.split('|').collect();
+		(fields[0], fields[fields.len() - 1])
+	} else if let Some(index) = line.find(['[', '"']) {
+		(&line[..index], &line[index..])
+	} else {
+		line.rsplit_once(' ')?
+	};
+
+	//epd style lines lack the move counters
+	let mut fen = fen.trim().trim_end_matches(';').trim().to_string();
+	if fen.split_whitespace().count() == 4 {
+		fen += " 0 1";
+	}
+
+	Some((Board::from_fen(&fen, false).ok()?, parse_result(result)?))
+}
+
+fn load_shard(lines: &[&str]) -> (Shard, f64) {
+	let mut shard = Shard::default();
+	let mut trace_error = 0.0;
+	let params = flatten_params(&EvalParams::DEFAULT);
+
+	for line in lines {
+		let (board, result) = match parse_line(line) {
+			Some(parsed) => parsed,
+			None => continue
+		};
+
+		let mut trace = Trace::ZERO;
+		let info = evaluate_traced(&board, &mut trace);
+
+		let start = shard.coefs.len();
+		let mut index = 0;
+		trace.visit(|_, coefs| {
+			for &coef in coefs {
+				if coef != 0 {
+					shard.coefs.push((index, coef as i16));
+				}
+				index += 1;
+			}
+		});
+
+		let entry = Entry {
+			start,
+			len: shard.coefs.len() - start,
+			phase: info.phase as f64,
+			scale: info.scale as f64,
+			result
+		};
+
+		//the trace should reproduce the evaluation up to rounding
+		let white_eval = if board.side_to_move() == Color::White { evaluate(&board, &EvalParams::DEFAULT) } else { -evaluate(&board, &EvalParams::DEFAULT) };
+		trace_error += (shard.eval(&entry, &params) - white_eval as f64).abs();
+
+		shard.entries.push(entry);
+	}
+
+	(shard, trace_error)
+}
+
+fn flatten_params(params: &EvalParams) -> Vec<[f64; 2]> {
+	let mut flat = Vec::new();
+	params.visit(|_, weights| {
+		flat.extend(weights.iter().map(|w| [w.mg() as f64, w.eg() as f64]));
+	});
+
+	flat
+}
+
+fn error(shards: &[Shard], params: &[[f64; 2]], k: f64) -> f64 {
+	let (sum, count) = thread::scope(|scope| {
+		let workers: Vec<_> = shards.iter().map(|shard| scope.spawn(move || {
+			shard.entries.iter().map(|entry| (entry.result - sigmoid(k, shard.eval(entry, params))).powi(2)).sum::<f64>()
+		})).collect();
+
+		let sum: f64 = workers.into_iter().map(|worker| worker.join().unwrap()).sum();
+		(sum, shards.iter().map(|shard| shard.entries.len()).sum::<usize>())
+	});
+
+	sum / count as f64
+}
+
+fn gradient(shards: &[Shard], params: &[[f64; 2]], k: f64) -> Vec<[f64; 2]> {
+	thread::scope(|scope| {
+		let workers: Vec<_> = shards.iter().map(|shard| scope.spawn(move || {
+			let mut gradient = vec![[0.0; 2]; params.len()];
+			let total_phase = Score::TOTAL_PHASE as f64;
+
+			for entry in &shard.entries {
+				let sig = sigmoid(k, shard.eval(entry, params));
+				let d_eval = -2.0 * (entry.result - sig) * sig * (1.0 - sig) * k * 10f64.ln() / 400.0 / entry.scale;
+
+				let mg_weight = d_eval * (total_phase - entry.phase) / total_phase;
+				let eg_weight = d_eval * entry.phase / total_phase;
+
+				for &(index, coef) in &shard.coefs[entry.start..entry.start + entry.len] {
+					gradient[index as usize][0] += mg_weight * coef as f64;
+					gradient[index as usize][1] += eg_weight * coef as f64;
+				}
+			}
+
+			gradient
+		})).collect();
+
+		let mut total = vec![[0.0; 2]; params.len()];
+		for worker in workers {
+			for (sum, part) in total.iter_mut().zip(worker.join().unwrap()) {
+				sum[0] += part[0];
+				sum[1] += part[1];
+			}
+		}
+
+		total
+	})
+}
+
+//golden section search for the sigmoid scaling that best fits the current weights
+fn find_k(shards: &[Shard], params: &[[f64; 2]]) -> f64 {
+	let ratio = (5f64.sqrt() - 1.0) / 2.0;
+	let mut low = 0.0;
+	let mut high = 5.0;
+
+	while high - low > 1e-4 {
+		let a = high - ratio * (high - low);
+		let b = low + ratio * (high - low);
+
+		if error(shards, params, a) < error(shards, params, b) {
+			high = b;
+		} else {
+			low = a;
+		}
+	}
+
+	(low + high) / 2.0
+}
+
+fn save(params: &[[f64; 2]], out: &str, params_out: &Option<String>) {
+	let mut terms = Vec::new();
+	let mut index = 0;
+
+	EvalParams::DEFAULT.visit(|name, weights| {
+		terms.push((name, weights.len() > 1, params[index..index + weights.len()].to_vec()));
+		index += weights.len();
+	});
+
+	fs::write(out, generate_eval_info(&terms)).unwrap();
+
+	if let Some(params_out) = params_out {
+		let mut rounded = EvalParams::DEFAULT;
+		let mut index = 0;
+
+		rounded.visit_mut(|_, weights| {
+			for weight in weights {
+				*weight = Score::new(params[index][0].round() as i32, params[index][1].round() as i32);
+				index += 1;
+			}
+		});
+
+		fs::write(params_out, rounded.text()).unwrap();
+	}
+}
+
+pub fn tune(args: Vec<String>, eval_params: &EvalParams) {
+	let dataset = match args.first() {
+		Some(dataset) => dataset.clone(),
+		None => {
+			println!("Usage: tune <dataset> [epochs N] [lr X] [threads N] [out PATH] [params PATH]");
+			return;
+		}
+	};
+
+	let mut epochs = EPOCHS;
+	let mut learning_rate = LEARNING_RATE;
+	let mut threads = thread::available_parallelism().map_or(1, |n| n.get());
+	let mut out = String::from(OUT);
+	let mut params_out = None;
+
+	for i in (1..args.len()).step_by(2) {
+		let value = match args.get(i + 1) {
+			Some(value) => value,
+			None => break
+		};
+
+		match args[i].as_str() {
+			"epochs" => epochs = value.parse().unwrap(),
+			"lr" => learning_rate = value.parse().unwrap(),
+			"threads" => threads = value.parse().unwrap(),
+			"out" => out = value.clone(),
+			"params" => params_out = Some(value.clone()),
+			_ => println!("Unknown tune option: {}", args[i])
+		}
+	}
+
+	let start = Instant::now();
+	let contents = fs::read_to_string(&dataset).unwrap();
+	let lines: Vec<&str> = contents.lines().filter(|line| !line.trim().is_empty()).collect();
+
+	let chunk_size = (lines.len() + threads - 1) / threads.max(1);
+	let (shards, trace_error): (Vec<Shard>, f64) = thread::scope(|scope| {
+		let workers: Vec<_> = lines.chunks(chunk_size.max(1)).map(|chunk| scope.spawn(move || load_shard(chunk))).collect();
+		let loaded: Vec<(Shard, f64)> = workers.into_iter().map(|worker| worker.join().unwrap()).collect();
+		let trace_error = loaded.iter().map(|(_, e)| e).sum();
+
+		(loaded.into_iter().map(|(shard, _)| shard).collect(), trace_error)
+	});
+
+	let positions: usize = shards.iter().map(|shard| shard.entries.len()).sum();
+	if positions == 0 {
+		println!("No positions could be read from {}", dataset);
+		return;
+	}
+
+	println!("Loaded {} positions in {:.1}s, mean trace error {:.3}", positions, start.elapsed().as_secs_f32(), trace_error / positions as f64);
+
+	let mut params = flatten_params(eval_params);
+	let k = find_k(&shards, &params);
+	println!("K {:.4} error {:.6}", k, error(&shards, &params, k));
+
+	//Adam
+	let mut momentum = vec![[0.0; 2]; params.len()];
+	let mut velocity = vec![[0.0; 2]; params.len()];
+
+	for epoch in 1..=epochs {
+		let gradient = gradient(&shards, &params, k);
+
+		for i in 0..params.len() {
+			for j in 0..2 {
+				let g = gradient[i][j] / positions as f64;
+				momentum[i][j] = BETA1 * momentum[i][j] + (1.0 - BETA1) * g;
+				velocity[i][j] = BETA2 * velocity[i][j] + (1.0 - BETA2) * g * g;
+
+				let m = momentum[i][j] / (1.0 - BETA1.powi(epoch as i32));
+				let v = velocity[i][j] / (1.0 - BETA2.powi(epoch as i32));
+				params[i][j] -= learning_rate * m / (v.sqrt() + EPSILON);
+			}
+		}
+
+		if epoch % REPORT_INTERVAL == 0 || epoch == epochs {
+			println!("epoch {} error {:.6} time {:.1}s", epoch, error(&shards, &params, k), start.elapsed().as_secs_f32());
+		}
+
+		if epoch % SAVE_INTERVAL == 0 {
+			save(&params, &out, &params_out);
+		}
+	}
+
+	save(&params, &out, &params_out);
+	println!("Wrote {}", out);
+}
@@ -5,6 +5,8 @@ use std::sync::atomic::AtomicBool;
 use std::time::Instant;
 
 use crate::search::search_master::*;
+use crate::search::stats::*;
+use crate::eval::terms::*;
 
 const DEPTH: i32 = 12;
 const HASH: u32 = 16;
@@ -40,12 +42,29 @@ const POSITIONS: &[&str] = &[
 ];
 
 //bench for engine identification for OpenBench support
-pub fn bench() {
-    let mut engine = Engine::new(HASH, THREADS);
+//bench threads N searches in deterministic mode, so the node count is a signature for that thread count too
+pub fn bench(args: Vec<String>, eval_params: &Arc<EvalParams>) {
+    let mut threads = THREADS;
+    if args.first().map(String::as_str) == Some("threads") {
+        threads = args.get(1).and_then(|value| value.parse().ok()).unwrap_or(THREADS).max(1);
+    }
+
+    let new_engine = || {
+        let mut engine = Engine::new(HASH, threads);
+        engine.boardwrapper.eval_params = eval_params.clone();
+        if threads > 1 {
+            engine.set_deterministic(true);
+        }
+
+        engine
+    };
+
+    let mut engine = new_engine();
     let mut placeholder_abort = Arc::new(AtomicBool::new(false));
 
     let mut total_nodes = 0;
     let mut total_elapsed: f32 = 0.0;
+    let mut total_stats = SearchStats::new();
 
     for i in 0..POSITIONS.len() {
         let now = Instant::now();
@@ -58,13 +77,19 @@ pub fn bench() {
         let _ = engine.go(time_control,placeholder_abort.clone());
 
         total_nodes += engine.nodes;
+        total_stats.merge(&engine.stats);
         total_elapsed += now.elapsed().as_secs_f32() * 1000_f32;
 
         //clear engine
-        engine = Engine::new(HASH, THREADS);
+        engine = new_engine();
         //placeholder_abort =  Arc::new(AtomicBool::new(false));
     }
 
+    //OpenBench reads the last line, so the stats come first
+    if SearchStats::ENABLED {
+        println!("info string {}", total_stats.report());
+    }
+
     let nps = ((total_nodes as f32 * 1000_f32) / total_elapsed) as u64;
     println!("{} nodes {} nps", total_nodes, nps);
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
 pub mod uci;
 pub mod bench;
-pub mod castle_parse;
\ No newline at end of file
+pub mod castle_parse;
+pub mod simgame;
\ No newline at end of file
@@ -0,0 +1,235 @@
+use cozy_chess::*;
+
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use crate::search::search_master::*;
+use crate::search::clock::*;
+use crate::datagen::selfplay::*;
+use crate::eval::terms::*;
+
+/*
+Self-play games under a simulated clock, to test time management without playing on the wall clock
+Usage: simgame [games N] [tc 40/120 | 1+0.01] [nps N] [plies N] [seed N]
+The tc is in seconds, either moves/base for repeating controls or base+increment
+Time is charged by nodes searched at the given nps, so the same arguments always play the same games
+Prints every game, the flag-falls and the average time used and left by move number
+*/
+
+const HASH: u32 = 16;
+const GAMES: u64 = 10;
+const TC: &str = "1+0.01";
+const NPS: u64 = 1000000;
+const RANDOM_PLIES: usize = 8;
+const SEED: u64 = 1;
+const MAX_PLIES: usize = 400;
+
+//moves per line of the time usage curve
+const CURVE_BUCKET: usize = 5;
+
+#[derive(Clone, Copy)]
+struct SimTimeControl {
+	moves: Option<u64>,
+	base: u64,
+	inc: u64
+}
+
+impl SimTimeControl {
+	fn parse(tc: &str) -> Option<SimTimeControl> {
+		let (moves, rest) = match tc.split_once('/') {
+			Some((moves, rest)) => (Some(moves.parse::<u64>().ok()?), rest),
+			None => (None, tc)
+		};
+
+		let (base, inc) = match rest.split_once('+') {
+			Some((base, inc)) => (base.parse::<f64>().ok()?, inc.parse::<f64>().ok()?),
+			None => (rest.parse::<f64>().ok()?, 0.0)
+		};
+
+		Some(SimTimeControl {
+			moves: moves.filter(|&moves| moves > 0),
+			base: (base * 1000.0) as u64,
+			inc: (inc * 1000.0) as u64
+		})
+	}
+}
+
+//time used and clock left after each move, summed per move number
+struct Curve {
+	used: Vec<u64>,
+	left: Vec<u64>,
+	samples: Vec<u64>
+}
+
+impl Curve {
+	fn new() -> Curve {
+		Curve {
+			used: vec![0; MAX_PLIES / 2 + 1],
+			left: vec![0; MAX_PLIES / 2 + 1],
+			samples: vec![0; MAX_PLIES / 2 + 1]
+		}
+	}
+
+	fn add(&mut self, move_number: usize, used: u64, left: u64) {
+		self.used[move_number] += used;
+		self.left[move_number] += left;
+		self.samples[move_number] += 1;
+	}
+
+	fn print(&self) {
+		println!("moves    avg used ms  avg left ms  samples");
+
+		for start in (1..self.samples.len()).step_by(CURVE_BUCKET) {
+			let end = (start + CURVE_BUCKET).min(self.samples.len());
+			let samples: u64 = self.samples[start..end].iter().sum();
+
+			if samples == 0 {
+				continue;
+			}
+
+			let used: u64 = self.used[start..end].iter().sum();
+			let left: u64 = self.left[start..end].iter().sum();
+			println!("{:>3}-{:<3}  {:>11}  {:>11}  {:>7}", start, end - 1, used / samples, left / samples, samples);
+		}
+	}
+}
+
+pub fn simgame(args: Vec<String>, eval_params: &Arc<EvalParams>) {
+	let mut games = GAMES;
+	let mut tc = String::from(TC);
+	let mut nps = NPS;
+	let mut random_plies = RANDOM_PLIES;
+	let mut seed = SEED;
+
+	for i in (0..args.len()).step_by(2) {
+		let value = match args.get(i + 1) {
+			Some(value) => value,
+			None => break
+		};
+
+		match args[i].as_str() {
+			"games" => games = value.parse().unwrap(),
+			"tc" => tc = value.clone(),
+			"nps" => nps = value.parse().unwrap(),
+			"plies" => random_plies = value.parse().unwrap(),
+			"seed" => seed = value.parse().unwrap(),
+			_ => println!("Unknown simgame option: {}", args[i])
+		}
+	}
+
+	let time_control = match SimTimeControl::parse(&tc) {
+		Some(time_control) => time_control,
+		None => {
+			println!("Invalid time control: {}", tc);
+			return;
+		}
+	};
+
+	println!("Simulating {} games at {} with {} nodes per second", games, tc, nps);
+
+	let mut rng = Rng::new(seed);
+	let mut curve = Curve::new();
+	let mut scores = [0_u64; 3];
+	let mut flags = 0;
+
+	for game in 0..games {
+		let (result, flagged, plies) = play_game(&mut rng, time_control, nps, random_plies, &mut curve, eval_params);
+
+		let index = if result == 1.0 { 0 } else if result == 0.5 { 1 } else { 2 };
+		scores[index] += 1;
+		if flagged.is_some() {
+			flags += 1;
+		}
+
+		let result_str = ["1-0", "1/2-1/2", "0-1"][index];
+		let flag_str = match flagged {
+			Some(Color::White) => " (white flagged)",
+			Some(Color::Black) => " (black flagged)",
+			None => ""
+		};
+		println!("game {} {} plies {}{}", game + 1, result_str, plies, flag_str);
+	}
+
+	println!("white {} draws {} black {} flag-falls {}", scores[0], scores[1], scores[2], flags);
+	curve.print();
+}
+
+//plays one game from a random opening, returning the white-relative result, who flagged and the plies played
+fn play_game(rng: &mut Rng, time_control: SimTimeControl, nps: u64, random_plies: usize, curve: &mut Curve, eval_params: &Arc<EvalParams>) -> (f32, Option<Color>, usize) {
+	let mut engine = Engine::new(HASH, 1);
+	engine.print_info = false;
+	engine.boardwrapper.eval_params = eval_params.clone();
+
+	//pick a random opening that is not already over
+	loop {
+		engine.boardwrapper.set_board(Board::default());
+		engine.my_past_positions = vec![engine.boardwrapper.board.hash()];
+
+		for _ in 0..random_plies {
+			let moves = legal_moves(&engine.boardwrapper.board);
+			if moves.is_empty() {
+				break;
+			}
+
+			play(&mut engine, moves[rng.below(moves.len())]);
+		}
+
+		if engine.boardwrapper.board.status() == GameStatus::Ongoing {
+			break;
+		}
+	}
+
+	let clock = SimulatedClock::new(nps);
+	let mut time_left = [time_control.base as i64; 2];
+	let mut moves_made = [0_u64; 2];
+	let mut ply = 0;
+
+	let (result, flagged) = loop {
+		let board = engine.boardwrapper.board.clone();
+		let us = board.side_to_move();
+
+		match board.status() {
+			GameStatus::Won => break (if us == Color::White { 0.0 } else { 1.0 }, None),
+			GameStatus::Drawn => break (0.5, None),
+			GameStatus::Ongoing => {}
+		}
+
+		if is_repetition(&engine.my_past_positions) || is_insufficient_material(&board) || ply >= MAX_PLIES {
+			break (0.5, None);
+		}
+
+		let mut search_control = TimeControl::new();
+		search_control.wtime = time_left[Color::White as usize];
+		search_control.btime = time_left[Color::Black as usize];
+		search_control.winc = time_control.inc as i64;
+		search_control.binc = time_control.inc as i64;
+		search_control.movestogo = time_control.moves.map(|moves| (moves - moves_made[us as usize] % moves) as i64);
+		search_control.simulated_nps = Some(nps);
+
+		engine.go(search_control, Arc::new(AtomicBool::new(false)));
+
+		let used = clock.elapsed(engine.nodes);
+		time_left[us as usize] -= used as i64;
+		if time_left[us as usize] < 0 {
+			break (if us == Color::White { 0.0 } else { 1.0 }, Some(us));
+		}
+
+		time_left[us as usize] += time_control.inc as i64;
+		moves_made[us as usize] += 1;
+
+		//a new period of the repeating control starts
+		if let Some(moves) = time_control.moves {
+			if moves_made[us as usize] % moves == 0 {
+				time_left[us as usize] += time_control.base as i64;
+			}
+		}
+
+		curve.add(moves_made[us as usize] as usize, used, time_left[us as usize] as u64);
+
+		let mv = engine.best_move.unwrap();
+		play(&mut engine, mv);
+		ply += 1;
+	};
+
+	(result, flagged, ply)
+}
@@ -8,16 +8,55 @@ use std::thread;
 
 use crate::search::search_master::*;
 use crate::uci::bench::*;
+use crate::uci::simgame::*;
 use crate::uci::castle_parse::*;
+use crate::datagen::selfplay::*;
+use crate::tune::tuner::*;
+use crate::eval::nnue::*;
+use crate::eval::terms::*;
+use crate::search::tunables::*;
+use crate::search::lmr_table::*;
 
 const HASH_MIN: u32 = 0;
 const HASH_MAX: u32 = 64000;
 const THREAD_MIN: u32 = 1;
 const THREAD_MAX: u32 = 2048;
+const MULTIPV_MIN: usize = 1;
+const MULTIPV_MAX: usize = 256;
+const MOVE_OVERHEAD_MIN: i64 = 0;
+const MOVE_OVERHEAD_MAX: i64 = 5000;
+
+#[derive(Clone)]
+struct EngineOptions {
+	hash: u32,
+	threads: u32,
+	multipv: usize,
+	move_overhead: i64,
+	deterministic: bool,
+	nnue: Option<Arc<Network>>,
+	use_nnue: bool,
+	eval_params: Arc<EvalParams>
+}
+
+impl EngineOptions {
+	fn create_engine(&self) -> Engine<'static> {
+		let mut engine = Engine::new(self.hash, self.threads);
+		engine.boardwrapper.eval_params = self.eval_params.clone();
+		engine.multipv = self.multipv;
+		engine.set_deterministic(self.deterministic);
+
+		//fall back to the hand crafted evaluation if there is no network or it is switched off
+		if self.use_nnue {
+			engine.boardwrapper.set_nnue(self.nnue.clone());
+		}
+
+		engine
+	}
+}
 
 enum UCICmd {
 	Uci,
-	UciNewGame(u32, u32),
+	UciNewGame(EngineOptions),
 	IsReady,
 	Go(TimeControl, Arc<AtomicBool>),
 	PositionFen(String),
@@ -33,8 +72,7 @@ fn get_channel() -> (Sender<UCICmd>, Arc<Mutex<Receiver<UCICmd>>>) {
 //uci command parser
 pub struct UCIMaster {
 	pub playing: bool,
-	hash: u32,
-	threads: u32,
+	options: EngineOptions,
 	engine_thread: Option<thread::JoinHandle<()>>,
 	stop_abort: Arc<AtomicBool>,
 	channel: (Sender<UCICmd>, Arc<Mutex<Receiver<UCICmd>>>)
@@ -49,17 +87,55 @@ impl UCIMaster {
 		println!("http://wbec-ridderkerk.nl/html/UCIProtocol.html");
 
 		let mut continue_engine = true;
+		let mut args: Vec<String> = std::env::args().skip(1).collect();
+		let mut eval_params = Arc::new(EvalParams::DEFAULT);
+
+		//load hand crafted eval weights for every mode if requested
+		if args.first().map(String::as_str) == Some("evalparams") && args.len() >= 2 {
+			match EvalParams::load(&args[1]) {
+				Ok(params) => eval_params = params,
+				Err(e) => println!("{}", e)
+			}
+
+			args.drain(..2);
+		}
 
 		//run bench if requested for OpenBench
-		if std::env::args().nth(1).as_deref() == Some("bench") {
-			bench();
+		if args.first().map(String::as_str) == Some("bench") {
+			bench(args[1..].to_vec(), &eval_params);
+			continue_engine = false;
+		}
+
+		//generate self-play training data if requested
+		if args.first().map(String::as_str) == Some("datagen") {
+			datagen(args[1..].to_vec(), &eval_params);
+			continue_engine = false;
+		}
+
+		//play games under a simulated clock to test time management if requested
+		if args.first().map(String::as_str) == Some("simgame") {
+			simgame(args[1..].to_vec(), &eval_params);
+			continue_engine = false;
+		}
+
+		//tune the hand crafted eval on a dataset if requested
+		if args.first().map(String::as_str) == Some("tune") {
+			tune(args[1..].to_vec(), &eval_params);
 			continue_engine = false;
 		}
 
 		UCIMaster {
 			playing: continue_engine,
-			hash: 16,
-			threads: 1,
+			options: EngineOptions {
+				hash: 16,
+				threads: 1,
+				multipv: 1,
+				move_overhead: 10,
+				deterministic: false,
+				nnue: None,
+				use_nnue: true,
+				eval_params
+			},
 			engine_thread: None,
 			stop_abort: Arc::new(AtomicBool::new(false)),
 			channel: get_channel()
@@ -78,10 +154,9 @@ impl UCIMaster {
 				if self.engine_thread.is_none() {
 					let thread_receiver = receiver.clone();
 
-					let init_hash_count = self.hash;
-					let init_thread_count = self.threads;
+					let init_options = self.options.clone();
 					self.engine_thread = Some(thread::spawn(move || {
-						let mut engine = Engine::new(init_hash_count, init_thread_count);
+						let mut engine = init_options.create_engine();
 						let mut playing = true;
 
 						loop {
@@ -92,10 +167,17 @@ impl UCIMaster {
 										println!("id author DkeRee");
 										println!("option name Hash type spin default 16 min 0 max 64000");
 										println!("option name Threads type spin default 1 min 1 max 2048");
+										println!("option name MultiPV type spin default 1 min 1 max 256");
+										println!("option name Move Overhead type spin default 10 min 0 max 5000");
+										println!("option name Deterministic type check default false");
+										println!("option name EvalFile type string default <empty>");
+										println!("option name UseNNUE type check default true");
+										println!("option name EvalParams type string default <empty>");
+										print_uci_options();
 										println!("uciok");
 									},
-									UCICmd::UciNewGame(hash_count, thread_count) => {
-										engine = Engine::new(hash_count, thread_count);
+									UCICmd::UciNewGame(options) => {
+										engine = options.create_engine();
 									},
 									UCICmd::IsReady => {
 										println!("readyok");
@@ -111,15 +193,19 @@ impl UCIMaster {
 										engine.my_past_positions.push(engine.boardwrapper.board.hash());
 									},
 									UCICmd::PositionPgn(pgn_vec, default) => {
+										let mut board = engine.boardwrapper.board.clone();
+
 										if default {
-											engine.boardwrapper.board = Board::default();
+											board = Board::default();
 											engine.my_past_positions = Vec::with_capacity(64);
 										}
 
 										for i in 0..pgn_vec.len() {
-											engine.boardwrapper.board.play_unchecked(_regular_to_960_(pgn_vec[i].clone(), &engine.boardwrapper.board).parse().unwrap());
-											engine.my_past_positions.push(engine.boardwrapper.board.hash());
+											board.play_unchecked(_regular_to_960_(pgn_vec[i].clone(), &board).parse().unwrap());
+											engine.my_past_positions.push(board.hash());
 										}
+
+										engine.boardwrapper.set_board(board);
 									},
 									UCICmd::Quit => {
 										playing = false;
@@ -143,7 +229,7 @@ impl UCIMaster {
 									let hash = cmd_vec[ind + 2].parse::<u32>().unwrap();
 
 									if HASH_MIN <= hash && hash <= HASH_MAX {
-										self.hash = hash;
+										self.options.hash = hash;
 									} else {
 										println!("Hash input is out of bounds. Retype the whole command.");
 									}
@@ -152,22 +238,94 @@ impl UCIMaster {
 									let thread = cmd_vec[ind + 2].parse::<u32>().unwrap();
 
 									if THREAD_MIN <= thread && thread <= THREAD_MAX {
-										self.threads = thread;
+										self.options.threads = thread;
 									} else {
 										println!("Thread input is out of bounds. Retype the whole command.");
 									}
+								},
+								"MultiPV" => {
+									let multipv = cmd_vec[ind + 2].parse::<usize>().unwrap();
+
+									if (MULTIPV_MIN..=MULTIPV_MAX).contains(&multipv) {
+										self.options.multipv = multipv;
+									} else {
+										println!("MultiPV input is out of bounds. Retype the whole command.");
+									}
+								},
+								"Move" if cmd_vec.get(ind + 1) == Some(&"Overhead") => {
+									let move_overhead = cmd_vec[ind + 3].parse::<i64>().unwrap();
+
+									if (MOVE_OVERHEAD_MIN..=MOVE_OVERHEAD_MAX).contains(&move_overhead) {
+										self.options.move_overhead = move_overhead;
+									} else {
+										println!("Move Overhead input is out of bounds. Retype the whole command.");
+									}
+								},
+								"EvalFile" => {
+									let path = cmd_vec[ind + 2..].join(" ");
+
+									if path.is_empty() || path == "<empty>" {
+										self.options.nnue = None;
+									} else {
+										match Network::load(&path) {
+											Ok(nnue) => {
+												self.options.nnue = Some(nnue);
+												println!("info string loaded network {}", path);
+											},
+											Err(e) => println!("info string {}", e)
+										}
+									}
+								},
+								"Deterministic" => {
+									self.options.deterministic = cmd_vec[ind + 2] == "true";
+								},
+								"UseNNUE" => {
+									self.options.use_nnue = cmd_vec[ind + 2] == "true";
+								},
+								"EvalParams" => {
+									let path = cmd_vec[ind + 2..].join(" ");
+
+									if path.is_empty() || path == "<empty>" {
+										self.options.eval_params = Arc::new(EvalParams::DEFAULT);
+									} else {
+										match EvalParams::load(&path) {
+											Ok(params) => {
+												self.options.eval_params = params;
+												println!("info string loaded eval params {}", path);
+											},
+											Err(e) => println!("info string {}", e)
+										}
+									}
+								}
+								name => {
+									if let Some(tunable) = find_tunable(name) {
+										let value = cmd_vec[ind + 2].parse::<i32>().unwrap();
+
+										if tunable.min <= value && value <= tunable.max {
+											tunable.set(value);
+										} else {
+											println!("{} input is out of bounds. Retype the whole command.", name);
+										}
+									}
 								}
-								_ => {}
 							}
 						}
 
-						sender.send(UCICmd::UciNewGame(self.hash, self.threads)).unwrap();
+						sender.send(UCICmd::UciNewGame(self.options.clone())).unwrap();
 					},
 					_ => {}
 				}
 			},
+			"spsa" => {
+				//list tunables for OpenBench SPSA
+				print_spsa_inputs();
+			},
+			"lmr" => {
+				//inspect the reductions generated from the current tunables
+				LMRTable::new().print();
+			},
 			"ucinewgame" => {
-				sender.send(UCICmd::UciNewGame(self.hash, self.threads)).unwrap();
+				sender.send(UCICmd::UciNewGame(self.options.clone())).unwrap();
 			},
 			"isready" => {
 				sender.send(UCICmd::IsReady).unwrap();
@@ -176,6 +334,7 @@ impl UCIMaster {
 				self.stop_abort = Arc::new(AtomicBool::new(false));
 
 				let mut time_control = TimeControl::new();
+				time_control.move_overhead = self.options.move_overhead;
 
 				for i in 1..cmd_vec.len() {
 					match cmd_vec[i] {
@@ -200,6 +359,9 @@ impl UCIMaster {
 						"movestogo" => {
 							time_control.movestogo = Some(cmd_vec[i + 1].parse::<i64>().unwrap());
 						},
+						"nodes" => {
+							time_control.nodes = Some(cmd_vec[i + 1].parse::<u64>().unwrap());
+						},
 						_ => {}
 					}
 				}
@@ -211,7 +373,7 @@ impl UCIMaster {
 					match cmd_vec[1] {
 						"startpos" => {
 							if cmd_vec.len() == 2 {
-								sender.send(UCICmd::UciNewGame(self.hash, self.threads)).unwrap();
+								sender.send(UCICmd::UciNewGame(self.options.clone())).unwrap();
 							} else {
 								let mut pgn_vec = Vec::with_capacity(64);
 								for i in 3..cmd_vec.len() {